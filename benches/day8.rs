@@ -0,0 +1,187 @@
+//! Compares the `Segments` bitmask Day 8 pipeline with the `HashSet<char>` one it replaced.
+
+use std::fs;
+
+use advent_of_code_2021::day8::{
+    determine_input_numbers, determine_output_number, parse_input_signals_and_output_digits,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// The pipeline as it was when each pattern was a set of letters, copied
+/// unchanged from before the switch to `Segments`.
+mod hash_set {
+    use std::{collections::HashSet, iter};
+
+    use advent_of_code_2021::{error::next_token, ParseError, Result};
+
+    pub type Entry = (Vec<HashSet<char>>, Vec<HashSet<char>>);
+
+    pub fn parse_input_signals_and_output_digits(input: &str) -> Result<Entry> {
+        // parse "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe"
+
+        let mut parts = input.split('|');
+        let signals = next_token(input, parts.next(), "signal patterns")?;
+
+        let mut input_signals = Vec::<HashSet<char>>::new();
+        for signal in signals.split_ascii_whitespace() {
+            input_signals.push(read_signal_pattern(input, signal)?);
+        }
+
+        let digits = next_token(input, parts.next(), "'|'")?;
+        let mut output_digits = Vec::<HashSet<char>>::new();
+        for digit_pattern in digits.split_ascii_whitespace() {
+            output_digits.push(read_signal_pattern(input, digit_pattern)?);
+        }
+
+        Ok((input_signals, output_digits))
+    }
+
+    fn read_signal_pattern(input: &str, pattern: &str) -> Result<HashSet<char>> {
+        // parse "cfbegad"
+        if !pattern.chars().all(|letter| ('a'..='g').contains(&letter)) {
+            return Err(ParseError::invalid_token(
+                input,
+                pattern,
+                "a signal pattern of the letters a to g",
+            ));
+        }
+
+        Ok(pattern.chars().collect())
+    }
+
+    pub fn determine_input_numbers(input: &[HashSet<char>]) -> Vec<HashSet<char>> {
+        let mut numbers: Vec<HashSet<char>> =
+            iter::repeat_with(HashSet::<char>::new).take(10).collect();
+
+        for some_num in input {
+            match some_num.len() {
+                2 => numbers[1] = some_num.to_owned(),
+                3 => numbers[7] = some_num.to_owned(),
+                4 => numbers[4] = some_num.to_owned(),
+                7 => numbers[8] = some_num.to_owned(),
+                _ => (),
+            };
+        }
+
+        for some_num in input {
+            if is_zero(&numbers[1], &numbers[4], some_num) {
+                numbers[0] = some_num.to_owned();
+            } else if is_two(&numbers[4], some_num) {
+                numbers[2] = some_num.to_owned();
+            } else if is_three(&numbers[1], some_num) {
+                numbers[3] = some_num.to_owned();
+            } else if is_five(&numbers[1], &numbers[4], some_num) {
+                numbers[5] = some_num.to_owned();
+            } else if is_six(&numbers[1], some_num) {
+                numbers[6] = some_num.to_owned();
+            } else if is_nine(&numbers[4], some_num) {
+                numbers[9] = some_num.to_owned();
+            }
+        }
+
+        numbers
+    }
+
+    pub fn determine_output_number(
+        input: &[HashSet<char>],
+        output_digits: &[HashSet<char>],
+    ) -> usize {
+        let mut number = String::new();
+
+        for digit in output_digits {
+            let index = input
+                .iter()
+                .position(|input_num| input_num == digit)
+                .unwrap();
+            number += &index.to_string();
+        }
+
+        number.parse::<usize>().unwrap()
+    }
+
+    fn is_zero(one: &HashSet<char>, four: &HashSet<char>, some_num: &HashSet<char>) -> bool {
+        some_num.len() == 6
+            && one.intersection(some_num).count() == one.len()
+            && four.intersection(some_num).count() == 3
+    }
+
+    fn is_two(four: &HashSet<char>, some_num: &HashSet<char>) -> bool {
+        some_num.len() == 5 && four.intersection(some_num).count() == 2
+    }
+
+    fn is_three(one: &HashSet<char>, some_num: &HashSet<char>) -> bool {
+        some_num.len() == 5 && one.intersection(some_num).count() == one.len()
+    }
+
+    fn is_five(one: &HashSet<char>, four: &HashSet<char>, some_num: &HashSet<char>) -> bool {
+        some_num.len() == 5
+            && one.intersection(some_num).count() == 1
+            && four.intersection(some_num).count() == 3
+    }
+
+    fn is_six(one: &HashSet<char>, some_num: &HashSet<char>) -> bool {
+        some_num.len() == 6 && one.intersection(some_num).count() == 1
+    }
+
+    fn is_nine(four: &HashSet<char>, some_num: &HashSet<char>) -> bool {
+        some_num.len() == 6 && four.intersection(some_num).count() == four.len()
+    }
+}
+
+fn bench_day8(c: &mut Criterion) {
+    let input = fs::read_to_string("./data/day8.txt").unwrap();
+
+    let mut group = c.benchmark_group("day8/segments");
+    group.bench_function("hash_set/parse", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .map(|line| hash_set::parse_input_signals_and_output_digits(line).unwrap())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("bitmask/parse", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .map(|line| parse_input_signals_and_output_digits(line).unwrap())
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let entries: Vec<hash_set::Entry> = input
+        .lines()
+        .map(|line| hash_set::parse_input_signals_and_output_digits(line).unwrap())
+        .collect();
+    group.bench_function("hash_set/decode", |b| {
+        b.iter(|| {
+            black_box(&entries)
+                .iter()
+                .map(|(signals, outputs)| {
+                    let input = hash_set::determine_input_numbers(signals);
+                    hash_set::determine_output_number(&input, outputs)
+                })
+                .sum::<usize>()
+        })
+    });
+
+    let entries: Vec<_> = input
+        .lines()
+        .map(|line| parse_input_signals_and_output_digits(line).unwrap())
+        .collect();
+    group.bench_function("bitmask/decode", |b| {
+        b.iter(|| {
+            black_box(&entries)
+                .iter()
+                .map(|(signals, outputs)| {
+                    let input = determine_input_numbers(signals);
+                    determine_output_number(&input, outputs).unwrap()
+                })
+                .sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_day8);
+criterion_main!(benches);
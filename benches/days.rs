@@ -0,0 +1,43 @@
+use std::{fs, path::Path};
+
+use advent_of_code_2021::{
+    day1::Day1, day10::Day10, day11::Day11, day2::Day2, day3::Day3, day4::Day4, day5::Day5,
+    day6::Day6, day7::Day7, day8::Day8, day9::Day9, input::discover_inputs, Day,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Benchmarks parsing and both parts of a day against one of its inputs.
+fn bench_day<D: Day>(c: &mut Criterion, dir: &Path, file_name: &str) {
+    let input = fs::read_to_string(dir.join(file_name)).unwrap();
+    let parsed = D::parse(&input).unwrap();
+
+    let mut group = c.benchmark_group(format!("day{}/{}", D::DAY, file_name));
+    group.bench_function("parse", |b| b.iter(|| D::parse(black_box(&input))));
+    group.bench_function("part1", |b| b.iter(|| D::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| D::part2(black_box(&parsed))));
+    group.finish();
+}
+
+fn bench_days(c: &mut Criterion) {
+    let dir = Path::new("./data");
+
+    for (day, file_name) in discover_inputs(dir).unwrap() {
+        match day {
+            1 => bench_day::<Day1>(c, dir, &file_name),
+            2 => bench_day::<Day2>(c, dir, &file_name),
+            3 => bench_day::<Day3>(c, dir, &file_name),
+            4 => bench_day::<Day4>(c, dir, &file_name),
+            5 => bench_day::<Day5>(c, dir, &file_name),
+            6 => bench_day::<Day6>(c, dir, &file_name),
+            7 => bench_day::<Day7>(c, dir, &file_name),
+            8 => bench_day::<Day8>(c, dir, &file_name),
+            9 => bench_day::<Day9>(c, dir, &file_name),
+            10 => bench_day::<Day10>(c, dir, &file_name),
+            11 => bench_day::<Day11>(c, dir, &file_name),
+            _ => (),
+        }
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -0,0 +1,205 @@
+//! The registry of expected answers that `aoc verify` checks the solvers against.
+//!
+//! Answers are stored in a small subset of TOML, one table per day and input file:
+//!
+//! ```toml
+//! [day9."day9.txt"]
+//! part1 = "444"
+//! part2 = "1168440"
+//! ```
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::{error::next_token, ParseError, Result};
+
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct Answers {
+    // (day, input file name) => [part 1, part 2]
+    answers: BTreeMap<(u8, String), [Option<String>; 2]>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut answers = Answers::default();
+        let mut current = None;
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                current = Some(read_table_header(line).map_err(|err| err.offset_lines(i))?);
+                continue;
+            }
+
+            let (day, input) = current
+                .clone()
+                .ok_or_else(|| ParseError::invalid_token(line, line, "a [dayN.\"input\"] table"))
+                .map_err(|err| err.offset_lines(i))?;
+            let (part, answer) = read_answer(line).map_err(|err| err.offset_lines(i))?;
+
+            answers.record(day, &input, part, answer);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        self.answers
+            .get(&(day, input.to_owned()))
+            .and_then(|parts| parts[part as usize - 1].as_deref())
+    }
+
+    pub fn record(&mut self, day: u8, input: &str, part: u8, answer: String) {
+        assert!(part == 1 || part == 2);
+
+        let parts = self.answers.entry((day, input.to_owned())).or_default();
+        parts[part as usize - 1] = Some(answer);
+    }
+
+    /// Every (day, input file name) pair with at least one recorded answer.
+    pub fn inputs(&self) -> impl Iterator<Item = (u8, &str)> {
+        self.answers
+            .keys()
+            .map(|(day, input)| (*day, input.as_str()))
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# Expected answers, keyed by day and input file. Update with `aoc verify --record`."
+        )?;
+
+        for ((day, input), parts) in &self.answers {
+            writeln!(f)?;
+            writeln!(f, "[day{}.\"{}\"]", day, input)?;
+            for (i, answer) in parts.iter().enumerate() {
+                if let Some(answer) = answer {
+                    writeln!(f, "part{} = \"{}\"", i + 1, answer)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn read_table_header(line: &str) -> Result<(u8, String)> {
+    // parse '[day9."day9.txt"]'
+    let expected = "a table header like [day9.\"day9.txt\"]";
+    let invalid = || ParseError::invalid_token(line, line, expected);
+
+    let inner = line
+        .strip_prefix("[day")
+        .and_then(|inner| inner.strip_suffix(']'))
+        .ok_or_else(invalid)?;
+    let (day, input) = inner.split_once('.').ok_or_else(invalid)?;
+
+    let day = day
+        .parse()
+        .map_err(|_| ParseError::invalid_token(line, day, "a day"))?;
+    let input = read_string(line, input)?;
+
+    Ok((day, input.to_owned()))
+}
+
+fn read_answer(line: &str) -> Result<(u8, String)> {
+    // parse 'part1 = "444"'
+    let mut parts = line.splitn(2, '=');
+
+    let key = next_token(line, parts.next(), "part1 or part2")?.trim();
+    let part = match key {
+        "part1" => 1,
+        "part2" => 2,
+        _ => return Err(ParseError::invalid_token(line, key, "part1 or part2")),
+    };
+
+    let value = next_token(line, parts.next(), "'='")?.trim();
+    Ok((part, read_string(line, value)?.to_owned()))
+}
+
+fn read_string<'a>(line: &str, value: &'a str) -> Result<&'a str> {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .filter(|value| !value.contains('"'))
+        .ok_or_else(|| ParseError::invalid_token(line, value, "a quoted string"))
+}
+
+#[cfg(test)]
+mod answers_tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"# Expected answers
+
+[day7."day7.txt"]
+part1 = "352331"
+part2 = "99266250"
+
+[day11."sample11_small.txt"]
+part2 = "6"
+"#;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        assert_eq!(Some("352331"), answers.get(7, "day7.txt", 1));
+        assert_eq!(Some("99266250"), answers.get(7, "day7.txt", 2));
+        assert_eq!(None, answers.get(11, "sample11_small.txt", 1));
+        assert_eq!(Some("6"), answers.get(11, "sample11_small.txt", 2));
+        assert_eq!(None, answers.get(8, "day8.txt", 1));
+
+        assert_eq!(
+            vec![(7, "day7.txt"), (11, "sample11_small.txt")],
+            answers.inputs().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(11, "day11.txt", 1, "1655".to_owned());
+        answers.record(2, "day2.txt", 2, "1942068080".to_owned());
+        answers.record(2, "day2.txt", 1, "2039912".to_owned());
+
+        let text = answers.to_string();
+        assert_eq!(
+            "# Expected answers, keyed by day and input file. Update with `aoc verify --record`.\n\
+             \n\
+             [day2.\"day2.txt\"]\n\
+             part1 = \"2039912\"\n\
+             part2 = \"1942068080\"\n\
+             \n\
+             [day11.\"day11.txt\"]\n\
+             part1 = \"1655\"\n",
+            text
+        );
+        assert_eq!(answers, Answers::parse(&text).unwrap());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let err = Answers::parse("[day7.\"day7.txt\"]\npart3 = \"1\"").unwrap_err();
+        assert_eq!(
+            "2:1: expected part1 or part2, found `part3`",
+            err.to_string()
+        );
+
+        let err = Answers::parse("part1 = \"1\"").unwrap_err();
+        assert_eq!(
+            "1:1: expected a [dayN.\"input\"] table, found `part1 = \"1\"`",
+            err.to_string()
+        );
+
+        let err = Answers::parse("[day7.\"day7.txt\"]\npart1 = 352331").unwrap_err();
+        assert_eq!(
+            "2:9: expected a quoted string, found `352331`",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,691 @@
+use std::{collections::BTreeSet, env, fs, io, iter, path::Path, process};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use advent_of_code_2021::{
+    answers::Answers,
+    day4::{format_bingo_game, rig_draw, BingoGenerator, Finish},
+    day5::{build_diagram, Backend, Day5, Diagram},
+    day6::{write_history_csv, write_history_json, CountOverflow, Day6, Fish},
+    day8::{format_notes, render_notes, Alphabet, Day8, NotesGenerator},
+    input::{discover_inputs, input_dir},
+    solution, Day, InputSource, Report, DAYS,
+};
+
+const USAGE: &str = "Usage: aoc run <day|all> [--part <1|2>] [--input <path|->] [--time]
+       aoc verify [--record]
+       aoc generate 4 [--seed <n>] [--count <boards>] [--first <board>|--last <board>]
+       aoc generate 8 [--seed <n>] [--count <lines>] [--alphabet <decimal|hex|alphanumeric>]
+       aoc render 5 [--input <path|->] [--output <file.ppm|file.png>]
+       aoc render 8 [--input <path|->]
+       aoc history 6 [--input <path|->] [--days <n>] [--json]";
+
+const ANSWERS_FILE: &str = "answers.toml";
+
+enum Command {
+    Run {
+        days: Vec<u8>,
+        parts: Vec<u8>,
+        input: Option<String>,
+        time: bool,
+    },
+    Verify {
+        record: bool,
+    },
+    Generate {
+        day: u8,
+        seed: Option<u64>,
+        count: Option<usize>,
+        rig: Option<(Finish, usize)>,
+        alphabet: Option<Alphabet>,
+    },
+    Render {
+        day: u8,
+        input: Option<String>,
+        output: Option<String>,
+    },
+    History {
+        day: u8,
+        input: Option<String>,
+        days: usize,
+        json: bool,
+    },
+}
+
+fn main() {
+    let command = match parse_args(env::args().skip(1).collect()) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Run {
+            days,
+            parts,
+            input,
+            time,
+        } => run(&days, &parts, input.as_deref(), time),
+        Command::Verify { record } => verify(record),
+        Command::Generate {
+            day,
+            seed,
+            count,
+            rig,
+            alphabet,
+        } => generate(day, seed, count, rig, alphabet),
+        Command::Render { day, input, output } => render(day, input.as_deref(), output.as_deref()),
+        Command::History {
+            day,
+            input,
+            days,
+            json,
+        } => history(day, input.as_deref(), days, json),
+    }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("run") => (),
+        Some("verify") => {
+            return match args.next().as_deref() {
+                None => Ok(Command::Verify { record: false }),
+                Some("--record") => Ok(Command::Verify { record: true }),
+                Some(arg) => Err(format!("Unknown argument '{}'", arg)),
+            }
+        }
+        Some("generate") => return parse_generate_args(args),
+        Some("render") => return parse_render_args(args),
+        Some("history") => return parse_history_args(args),
+        Some(command) => return Err(format!("Unknown command '{}'", command)),
+        None => return Err("Missing command".to_owned()),
+    }
+
+    let days = match args.next().as_deref() {
+        Some("all") => DAYS.to_vec(),
+        Some(day) => match day.parse::<u8>() {
+            Ok(day) if DAYS.contains(&day) => vec![day],
+            _ => return Err(format!("No solution for day '{}'", day)),
+        },
+        None => return Err("Missing day".to_owned()),
+    };
+
+    let mut parts = vec![1, 2];
+    let mut input = None;
+    let mut time = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => match args.next().as_deref() {
+                Some("1") => parts = vec![1],
+                Some("2") => parts = vec![2],
+                _ => return Err("--part must be 1 or 2".to_owned()),
+            },
+            "--input" => match args.next() {
+                Some(path) if days.len() == 1 => input = Some(path),
+                Some(_) => return Err("--input can only be used with a single day".to_owned()),
+                None => return Err("--input needs a path, or - for stdin".to_owned()),
+            },
+            "--time" => time = true,
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::Run {
+        days,
+        parts,
+        input,
+        time,
+    })
+}
+
+fn parse_generate_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let day = match args.next().as_deref() {
+        Some("4") => 4,
+        Some("8") => 8,
+        Some(day) => return Err(format!("No generator for day '{}'", day)),
+        None => return Err("Missing day".to_owned()),
+    };
+
+    let mut seed = None;
+    let mut count = None;
+    let mut rig = None;
+    let mut alphabet = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => match args.next().map(|seed| seed.parse()) {
+                Some(Ok(value)) => seed = Some(value),
+                _ => return Err("--seed must be a number".to_owned()),
+            },
+            "--count" => match args.next().map(|count| count.parse()) {
+                Some(Ok(value)) => count = Some(value),
+                _ => return Err("--count must be a number".to_owned()),
+            },
+            "--first" | "--last" if day != 4 => {
+                return Err(format!("{} only works for day 4", arg))
+            }
+            "--first" | "--last" => {
+                let finish = if arg == "--first" {
+                    Finish::First
+                } else {
+                    Finish::Last
+                };
+                match args.next().map(|board| board.parse()) {
+                    Some(Ok(board)) => rig = Some((finish, board)),
+                    _ => return Err(format!("{} needs a board number", arg)),
+                }
+            }
+            "--alphabet" if day != 8 => return Err("--alphabet only works for day 8".to_owned()),
+            "--alphabet" => match args.next().as_deref() {
+                Some("decimal") => alphabet = Some(Alphabet::DECIMAL),
+                Some("hex") => alphabet = Some(Alphabet::HEXADECIMAL),
+                Some("alphanumeric") => alphabet = Some(Alphabet::ALPHANUMERIC),
+                _ => return Err("--alphabet must be decimal, hex or alphanumeric".to_owned()),
+            },
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::Generate {
+        day,
+        seed,
+        count,
+        rig,
+        alphabet,
+    })
+}
+
+fn parse_render_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let day = match args.next().as_deref() {
+        Some("5") => 5,
+        Some("8") => 8,
+        Some(day) => return Err(format!("No renderer for day '{}'", day)),
+        None => return Err("Missing day".to_owned()),
+    };
+
+    let mut input = None;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => match args.next() {
+                Some(path) => input = Some(path),
+                None => return Err("--input needs a path, or - for stdin".to_owned()),
+            },
+            "--output" => match args.next() {
+                Some(_) if day != 5 => return Err("--output only works for day 5".to_owned()),
+                Some(path) => output = Some(path),
+                None => return Err("--output needs a path".to_owned()),
+            },
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::Render { day, input, output })
+}
+
+fn parse_history_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let day = match args.next().as_deref() {
+        Some("6") => 6,
+        Some(day) => return Err(format!("No history for day '{}'", day)),
+        None => return Err("Missing day".to_owned()),
+    };
+
+    let mut input = None;
+    let mut days = 256;
+    let mut json = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => match args.next() {
+                Some(path) => input = Some(path),
+                None => return Err("--input needs a path, or - for stdin".to_owned()),
+            },
+            "--days" => match args.next().map(|days| days.parse()) {
+                Some(Ok(value)) => days = value,
+                _ => return Err("--days must be a number".to_owned()),
+            },
+            "--json" => json = true,
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::History {
+        day,
+        input,
+        days,
+        json,
+    })
+}
+
+fn run(days: &[u8], parts: &[u8], input_path: Option<&str>, time: bool) {
+    let mut rows = Vec::with_capacity(days.len());
+    let mut failed = false;
+
+    for day in days {
+        let source = InputSource::resolve(*day, input_path);
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!(
+                    "Could not read input for day {} from {}: {}",
+                    day, source, err
+                );
+                process::exit(1);
+            }
+        };
+
+        let report = solve(*day, &source, &input, parts);
+        failed |= report.is_none();
+        let mut row = vec![day.to_string()];
+        row.extend(answer_cells(&report));
+        if time {
+            row.extend(timing_cells(&report));
+        }
+        rows.push(row);
+    }
+
+    if time {
+        print_table(
+            &[
+                "Day",
+                "Part 1",
+                "Part 2",
+                "Parse",
+                "Part 1 time",
+                "Part 2 time",
+            ],
+            &rows,
+        );
+    } else {
+        print_table(&["Day", "Part 1", "Part 2"], &rows);
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Solves the requested parts of a day, reporting a diagnostic if the input cannot be parsed.
+fn solve(day: u8, source: &InputSource, input: &str, parts: &[u8]) -> Option<Report> {
+    match solution(day).unwrap().report(input, parts) {
+        Ok(report) => Some(report),
+        Err(err) => {
+            eprint!("{}", err.with_file(source.name()).render(input));
+            None
+        }
+    }
+}
+
+/// The answer to each part, with "-" for parts that were not solved and "error" for both
+/// parts when the input could not be parsed.
+fn answer_cells(report: &Option<Report>) -> Vec<String> {
+    match report {
+        Some(report) => report
+            .parts
+            .iter()
+            .map(|part| match part {
+                Some((answer, _)) => answer.clone(),
+                None => "-".to_owned(),
+            })
+            .collect(),
+        None => vec!["error".to_owned(), "error".to_owned()],
+    }
+}
+
+/// How long parsing and each part took, with "-" for anything that did not run.
+fn timing_cells(report: &Option<Report>) -> Vec<String> {
+    match report {
+        Some(report) => iter::once(format!("{:.2?}", report.parse_time))
+            .chain(report.parts.iter().map(|part| match part {
+                Some((_, elapsed)) => format!("{:.2?}", elapsed),
+                None => "-".to_owned(),
+            }))
+            .collect(),
+        None => vec!["-".to_owned(), "-".to_owned(), "-".to_owned()],
+    }
+}
+
+fn verify(record: bool) {
+    let dir = input_dir();
+    let answers_path = dir.join(ANSWERS_FILE);
+
+    let mut answers = match load_answers(&answers_path) {
+        Ok(answers) => answers,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    };
+
+    // Check every input on disk, plus any recorded input that has since gone missing
+    let mut inputs: BTreeSet<(u8, String)> = match discover_inputs(&dir) {
+        Ok(inputs) => inputs.into_iter().collect(),
+        Err(err) => {
+            eprintln!("Could not list inputs in {}: {}", dir.display(), err);
+            process::exit(1);
+        }
+    };
+    inputs.extend(answers.inputs().map(|(day, input)| (day, input.to_owned())));
+
+    let mut rows = Vec::with_capacity(inputs.len());
+    let mut mismatches = Vec::new();
+
+    for (day, file_name) in &inputs {
+        let mut row = vec![day.to_string(), file_name.clone()];
+
+        if solution(*day).is_none() {
+            row.extend(["no solver".to_owned(), "no solver".to_owned()]);
+            rows.push(row);
+            continue;
+        }
+
+        let source = InputSource::File(dir.join(file_name));
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(err) => {
+                mismatches.push(format!("Could not read {}: {}", source, err));
+                row.extend(["missing".to_owned(), "missing".to_owned()]);
+                rows.push(row);
+                continue;
+            }
+        };
+
+        let actual = answer_cells(&solve(*day, &source, &input, &[1, 2]));
+        for (i, actual) in actual.into_iter().enumerate() {
+            let part = i as u8 + 1;
+            let expected = answers.get(*day, file_name, part).map(|s| s.to_owned());
+
+            // an answer the solver couldn't give is never recorded
+            let failure = actual.strip_prefix("error: ");
+            let status = match &expected {
+                _ if actual == "error" || failure.is_some() => "error",
+                Some(expected) if *expected == actual => "pass",
+                Some(_) if record => "updated",
+                None if record => "recorded",
+                Some(_) => "FAIL",
+                None => "-",
+            };
+
+            match status {
+                "error" => mismatches.push(format!(
+                    "Day {} {} part {}: {}",
+                    day,
+                    file_name,
+                    part,
+                    failure.unwrap_or("input could not be parsed")
+                )),
+                "FAIL" => mismatches.push(format!(
+                    "Day {} {} part {}: expected {}, got {}",
+                    day,
+                    file_name,
+                    part,
+                    expected.unwrap(),
+                    actual
+                )),
+                "updated" | "recorded" => answers.record(*day, file_name, part, actual),
+                _ => (),
+            }
+
+            row.push(status.to_owned());
+        }
+
+        rows.push(row);
+    }
+
+    print_table(&["Day", "Input", "Part 1", "Part 2"], &rows);
+
+    if record {
+        if let Err(err) = fs::write(&answers_path, answers.to_string()) {
+            eprintln!("Could not write {}: {}", answers_path.display(), err);
+            process::exit(1);
+        }
+        println!("Recorded answers in {}", answers_path.display());
+    }
+
+    if !mismatches.is_empty() {
+        println!();
+        for mismatch in &mismatches {
+            println!("{}", mismatch);
+        }
+        process::exit(1);
+    }
+}
+
+/// Prints a random puzzle input, reporting the seed so it can be generated again.
+fn generate(
+    day: u8,
+    seed: Option<u64>,
+    count: Option<usize>,
+    rig: Option<(Finish, usize)>,
+    alphabet: Option<Alphabet>,
+) {
+    let seed = seed.unwrap_or_else(rand::random);
+    eprintln!("Generating day {} input with seed {}", day, seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    match day {
+        4 => generate_bingo(&mut rng, count, rig),
+        _ => generate_notes(&mut rng, count, alphabet),
+    }
+}
+
+fn generate_bingo(rng: &mut StdRng, count: Option<usize>, rig: Option<(Finish, usize)>) {
+    let mut generator = BingoGenerator::default();
+    if let Some(count) = count {
+        generator.boards = count;
+    }
+    let (mut draw, boards) = generator.generate(rng);
+
+    if let Some((finish, board)) = rig {
+        if board >= boards.len() {
+            eprintln!("There is no board {}, boards are numbered from 0", board);
+            process::exit(2);
+        }
+
+        draw = match rig_draw(&draw, &boards, board, finish) {
+            Some(draw) => draw,
+            None => {
+                eprintln!("Could not rig the draw for board {}", board);
+                process::exit(1);
+            }
+        };
+    }
+
+    print!("{}", format_bingo_game(&draw, &boards));
+}
+
+/// Prints random day 8 notes, reporting the answers they should give on stderr.
+fn generate_notes(rng: &mut StdRng, count: Option<usize>, alphabet: Option<Alphabet>) {
+    let mut generator = NotesGenerator::default();
+    if let Some(count) = count {
+        generator.lines = count;
+    }
+    if let Some(alphabet) = alphabet {
+        generator.alphabet = alphabet;
+    }
+    let notes = generator.generate(rng);
+
+    print!("{}", format_notes(&notes.entries));
+    eprintln!("Part 1: {}", notes.unique_outputs());
+    eprintln!("Part 2: {}", notes.sum());
+}
+
+/// Draws a day's input: the day 5 vent diagram, or the day 8 displays.
+fn render(day: u8, input_path: Option<&str>, output: Option<&str>) {
+    let source = InputSource::resolve(day, input_path);
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!(
+                "Could not read input for day {} from {}: {}",
+                day, source, err
+            );
+            process::exit(1);
+        }
+    };
+
+    match day {
+        5 => render_vents(&source, &input, output),
+        _ => render_displays(&source, &input),
+    }
+}
+
+/// Draws the vent diagram, including diagonals, as text or as an image.
+fn render_vents(source: &InputSource, input: &str, output: Option<&str>) {
+    let line_segments = match Day5::parse(input) {
+        Ok(line_segments) => line_segments,
+        Err(err) => {
+            eprint!("{}", err.with_file(source.name()).render(input));
+            process::exit(1);
+        }
+    };
+    let diagram = build_diagram(&line_segments, true, Backend::Sparse);
+    if let Err(err) = diagram.counts() {
+        eprintln!("Could not draw {}: {}", source, err);
+        process::exit(1);
+    }
+
+    let path = match output {
+        Some(path) => path,
+        None => {
+            println!("{}", diagram);
+            return;
+        }
+    };
+
+    let written = if path.ends_with(".png") {
+        write_png(&diagram, path)
+    } else {
+        create(path).and_then(|file| diagram.write_ppm(file).map_err(|err| err.to_string()))
+    };
+
+    if let Err(err) = written {
+        eprintln!("Could not write {}: {}", path, err);
+        process::exit(1);
+    }
+}
+
+/// Prints what the day 8 decoder made of each line of notes.
+fn render_displays(source: &InputSource, input: &str) {
+    let entries = match Day8::parse(input) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprint!("{}", err.with_file(source.name()).render(input));
+            process::exit(1);
+        }
+    };
+
+    print!("{}", render_notes(input, &entries));
+}
+
+#[cfg(feature = "png")]
+fn write_png(diagram: &Diagram, path: &str) -> Result<(), String> {
+    create(path).and_then(|file| diagram.write_png(file).map_err(|err| err.to_string()))
+}
+
+#[cfg(not(feature = "png"))]
+fn write_png(_: &Diagram, _: &str) -> Result<(), String> {
+    Err("PNG output needs the `png` feature, try a .ppm file instead".to_owned())
+}
+
+fn create(path: &str) -> Result<io::BufWriter<fs::File>, String> {
+    fs::File::create(path)
+        .map(io::BufWriter::new)
+        .map_err(|err| err.to_string())
+}
+
+/// Prints the number of fish of each age after every day, reporting the long run growth rate.
+fn history(day: u8, input_path: Option<&str>, days: usize, json: bool) {
+    let source = InputSource::resolve(day, input_path);
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!(
+                "Could not read input for day {} from {}: {}",
+                day, source, err
+            );
+            process::exit(1);
+        }
+    };
+
+    let ages = match Day6::parse(&input) {
+        Ok(ages) => ages,
+        Err(err) => {
+            eprint!("{}", err.with_file(source.name()).render(&input));
+            process::exit(1);
+        }
+    };
+
+    // with the lanternfish rules, u128 lasts for 951 days of data/day6.txt,
+    // and the history stops short after that
+    let mut fish = Fish::<u128>::from_ages(&ages);
+    eprintln!("Growth rate: {:.6} per day", fish.growth_rate());
+
+    let stdout = io::stdout().lock();
+    let mut simulated = 0;
+    let history = fish.history().take(days).inspect(|_| simulated += 1);
+    let written = if json {
+        write_history_json(stdout, history)
+    } else {
+        write_history_csv(stdout, history)
+    };
+
+    if let Err(err) = written {
+        eprintln!("Could not write history: {}", err);
+        process::exit(1);
+    }
+    if simulated < days {
+        eprintln!("Stopped after day {}: {}", simulated, CountOverflow);
+        process::exit(1);
+    }
+}
+
+fn load_answers(path: &Path) -> Result<Answers, String> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+        Err(err) => return Err(format!("Could not read {}: {}", path.display(), err)),
+    };
+
+    Answers::parse(&text).map_err(|err| err.with_file(path).render(&text))
+}
+
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].len())
+                .chain([headers[col].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
+
+    println!("{}", format_row(headers.to_vec()));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<String>>()
+            .join("-+-")
+    );
+    for row in rows {
+        println!(
+            "{}",
+            format_row(row.iter().map(|cell| cell.as_str()).collect())
+        );
+    }
+}
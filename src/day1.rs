@@ -0,0 +1,139 @@
+use crate::{error::parse_token, Day, Result};
+
+pub struct Day1;
+
+impl Day for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<u32>;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_measurements(input)
+    }
+
+    fn part1(measurements: &Self::Input) -> Self::Output {
+        calculate_num_increases(measurements)
+    }
+
+    fn part2(measurements: &Self::Input) -> Self::Output {
+        calculate_window_increases(measurements)
+    }
+}
+
+pub fn parse_measurements(input: &str) -> Result<Vec<u32>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, depth)| parse_token(depth, depth, "a depth").map_err(|err| err.offset_lines(i)))
+        .collect()
+}
+
+pub fn calculate_num_increases(measurements: &[u32]) -> u32 {
+    let mut current_depth: u32 = 0;
+    let mut num_increases: u32 = 0;
+
+    for depth in measurements {
+        if *depth > current_depth {
+            num_increases += 1;
+        }
+
+        current_depth = *depth;
+    }
+
+    num_increases.saturating_sub(1)
+}
+
+pub fn calculate_window_increases(measurements: &[u32]) -> u32 {
+    let mut current_window_sum: u32 = 0;
+    let mut num_increases: u32 = 0;
+
+    for depths in measurements.windows(3) {
+        let sum: u32 = depths.iter().sum();
+        if sum > current_window_sum {
+            num_increases += 1;
+        }
+
+        current_window_sum = sum;
+    }
+
+    num_increases.saturating_sub(1)
+}
+
+#[cfg(test)]
+mod day1_tests {
+    use super::*;
+    mod test_parse_measurements {
+        use super::*;
+
+        #[test]
+        fn valid() {
+            assert_eq!(
+                parse_measurements("199\n200\n208").unwrap(),
+                vec![199, 200, 208]
+            );
+        }
+
+        #[test]
+        fn invalid_depth() {
+            let err = parse_measurements("199\n2x0\n208").unwrap_err();
+            assert_eq!(err.to_string(), "2:1: expected a depth, found `2x0`");
+        }
+    }
+
+    mod test_calculate_num_increases {
+        use super::*;
+
+        #[test]
+        fn empty_vec() {
+            let measurements = Vec::new();
+            assert_eq!(calculate_num_increases(&measurements), 0);
+        }
+
+        #[test]
+        fn vec_with_one_item() {
+            let measurements = Vec::new();
+            assert_eq!(calculate_num_increases(&measurements), 0);
+        }
+
+        #[test]
+        fn no_increases() {
+            let measurements = vec![5, 4, 3, 2, 1, 0];
+            assert_eq!(calculate_num_increases(&measurements), 0);
+        }
+
+        #[test]
+        fn some_increases() {
+            let measurements = vec![5, 4, 6, 7, 3, 8];
+            assert_eq!(calculate_num_increases(&measurements), 3);
+        }
+    }
+
+    mod test_calculate_window_increases {
+        use super::*;
+
+        #[test]
+        fn empty_vec() {
+            let measurements = Vec::new();
+            assert_eq!(calculate_window_increases(&measurements), 0);
+        }
+
+        #[test]
+        fn vec_with_one_item() {
+            let measurements = Vec::new();
+            assert_eq!(calculate_window_increases(&measurements), 0);
+        }
+
+        #[test]
+        fn no_increases() {
+            let measurements = vec![5, 4, 3, 2, 1, 0];
+            assert_eq!(calculate_window_increases(&measurements), 0);
+        }
+
+        #[test]
+        fn some_increases() {
+            let measurements = vec![5, 4, 6, 7, 3, 8];
+            assert_eq!(calculate_window_increases(&measurements), 2);
+        }
+    }
+}
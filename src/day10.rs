@@ -0,0 +1,275 @@
+use std::{collections::VecDeque, error::Error, fmt};
+
+use crate::{Day, Fallible, ParseError};
+
+pub struct Day10;
+
+impl Day for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Result<String, char>>;
+    type Output = Fallible<usize, NoIncompleteLine>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        let mut results = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            check_chunk_characters(line).map_err(|err| err.offset_lines(i))?;
+            results.push(parse_line(line));
+        }
+
+        if results.is_empty() {
+            return Err(ParseError::unexpected_end(input, "a line of chunks"));
+        }
+
+        Ok(results)
+    }
+
+    fn part1(results: &Self::Input) -> Self::Output {
+        Fallible(Ok(get_syntax_error_score(results)))
+    }
+
+    fn part2(results: &Self::Input) -> Self::Output {
+        Fallible(get_middle_autocomplete_score(results).ok_or(NoIncompleteLine))
+    }
+}
+
+/// Every line is corrupted, so there are no autocomplete scores to pick from.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct NoIncompleteLine;
+
+impl fmt::Display for NoIncompleteLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no line is incomplete")
+    }
+}
+
+impl Error for NoIncompleteLine {}
+
+pub fn get_syntax_error_score(results: &[Result<String, char>]) -> usize {
+    let mut score: usize = 0;
+
+    for result in results {
+        if result.is_err() {
+            match result.as_ref().err().unwrap() {
+                ')' => score += 3,
+                ']' => score += 57,
+                '}' => score += 1197,
+                '>' => score += 25137,
+                _ => (),
+            }
+        }
+    }
+
+    score
+}
+
+pub fn get_autocomplete_scores(results: &[Result<String, char>]) -> Vec<usize> {
+    let mut scores = Vec::with_capacity(results.len());
+
+    for result in results {
+        if result.is_ok() {
+            let autocomplete = result.as_ref().unwrap();
+            let mut score: usize = 0;
+            for token in autocomplete.chars() {
+                score *= 5;
+                match token {
+                    ')' => score += 1,
+                    ']' => score += 2,
+                    '}' => score += 3,
+                    '>' => score += 4,
+                    _ => (),
+                }
+            }
+
+            scores.push(score);
+        }
+    }
+
+    scores
+}
+
+/// The middle autocomplete score, or `None` if no line is incomplete.
+pub fn get_middle_autocomplete_score(results: &[Result<String, char>]) -> Option<usize> {
+    let mut autocomplete_scores = get_autocomplete_scores(results);
+    autocomplete_scores.sort_unstable();
+
+    autocomplete_scores
+        .get(autocomplete_scores.len() / 2)
+        .copied()
+}
+
+pub fn check_chunk_characters(line: &str) -> crate::Result<()> {
+    match line
+        .char_indices()
+        .find(|(_, chr)| !"()[]{}<>".contains(*chr))
+    {
+        Some((i, chr)) => Err(ParseError::invalid_token(
+            line,
+            &line[i..i + chr.len_utf8()],
+            "one of ()[]{}<>",
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn parse_line(line: &str) -> Result<String, char> {
+    // [({(<(())[]>[[{[]{<()<>>
+    let mut token_stack = VecDeque::new();
+
+    for token in line.chars() {
+        match token {
+            '[' | '(' | '{' | '<' => {
+                token_stack.push_back(token);
+            }
+            ']' => {
+                let top = token_stack.pop_back();
+                if top != Some('[') {
+                    return Err(']');
+                }
+            }
+            ')' => {
+                let top = token_stack.pop_back();
+                if top != Some('(') {
+                    return Err(')');
+                }
+            }
+            '}' => {
+                let top = token_stack.pop_back();
+                if top != Some('{') {
+                    return Err('}');
+                }
+            }
+            '>' => {
+                let top = token_stack.pop_back();
+                if top != Some('<') {
+                    return Err('>');
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // Line is not corrupted, just incomplete
+    let mut autocomplete: String = String::new();
+
+    while !token_stack.is_empty() {
+        let token = token_stack.pop_back().unwrap();
+        match token {
+            '[' => autocomplete += "]",
+            '(' => autocomplete += ")",
+            '{' => autocomplete += "}",
+            '<' => autocomplete += ">",
+            _ => (),
+        }
+    }
+
+    Ok(autocomplete)
+}
+
+#[cfg(test)]
+mod day10_tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parse_line_corrupted() {
+        let line = "{([(<{}[<>[]}>{[]{[(<()>";
+        let result = parse_line(line);
+        assert_eq!('}', result.err().unwrap());
+
+        let line = "[[<[([]))<([[{}[[()]]]";
+        let result = parse_line(line);
+        assert_eq!(')', result.err().unwrap());
+
+        let line = "[{[{({}]{}}([{[{{{}}([]";
+        let result = parse_line(line);
+        assert_eq!(']', result.err().unwrap());
+
+        let line = "[<(<(<(<{}))><([]([]()";
+        let result = parse_line(line);
+        assert_eq!(')', result.err().unwrap());
+
+        let line = "<{([([[(<>()){}]>(<<{{";
+        let result = parse_line(line);
+        assert_eq!('>', result.err().unwrap());
+    }
+
+    #[test]
+    fn test_parse_line_unmatched_close() {
+        let result = parse_line("()]");
+        assert_eq!(']', result.err().unwrap());
+    }
+
+    #[test]
+    fn test_check_chunk_characters() {
+        assert!(check_chunk_characters("[({(<(())[]>[[{[]{<()<>>").is_ok());
+
+        let err = check_chunk_characters("[({(<(x)[]>").unwrap_err();
+        assert_eq!("1:7: expected one of ()[]{}<>, found `x`", err.to_string());
+    }
+
+    #[test]
+    fn test_parse_line_valid() {
+        let line = "[({(<(())[]>[[{[]{<()<>>";
+        let result = parse_line(line);
+        assert!(result.is_ok());
+        assert_eq!("}}]])})]", result.unwrap());
+
+        let line = "[(()[<>])]({[<{<<[]>>(";
+        let result = parse_line(line);
+        assert!(result.is_ok());
+        assert_eq!(")}>]})", result.unwrap());
+    }
+
+    #[test]
+    fn test_get_syntax_error_score() {
+        let input = fs::read_to_string("./data/sample10.txt").unwrap();
+
+        let results: Vec<Result<String, char>> = input.lines().map(parse_line).collect();
+
+        let score = get_syntax_error_score(&results);
+        assert_eq!(26397, score);
+    }
+
+    #[test]
+    fn test_get_autocomplete_scores() {
+        let input = fs::read_to_string("./data/sample10.txt").unwrap();
+
+        let results: Vec<Result<String, char>> = input.lines().map(parse_line).collect();
+
+        let autocomplete_scores = get_autocomplete_scores(&results);
+
+        assert_eq!(5, autocomplete_scores.len());
+        assert_eq!(288957, autocomplete_scores[0]);
+        assert_eq!(5566, autocomplete_scores[1]);
+        assert_eq!(1480781, autocomplete_scores[2]);
+        assert_eq!(995444, autocomplete_scores[3]);
+        assert_eq!(294, autocomplete_scores[4]);
+    }
+
+    #[test]
+    fn test_get_middle_autocomplete_score() {
+        let input = fs::read_to_string("./data/sample10.txt").unwrap();
+        let results = Day10::parse(&input).unwrap();
+        assert_eq!(Some(288957), get_middle_autocomplete_score(&results));
+        assert_eq!(Fallible(Ok(288957)), Day10::part2(&results));
+
+        let results = Day10::parse("{([(<{}[<>[]}>{[]{[(<()>\n()]").unwrap();
+        assert_eq!(None, get_middle_autocomplete_score(&results));
+        assert_eq!(Fallible(Ok(1197 + 57)), Day10::part1(&results));
+        assert_eq!(
+            "error: no line is incomplete",
+            Day10::part2(&results).to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let err = Day10::parse("").unwrap_err();
+        assert_eq!(
+            "1:1: expected a line of chunks, found end of line",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{parse, Day, Grid, Result};
+
+pub struct Day11;
+
+impl Day for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Grid<i8>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        build_grid(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Self::Output {
+        let mut grid = grid.clone();
+        (0..100).map(|_| step(&mut grid)).sum()
+    }
+
+    fn part2(grid: &Self::Input) -> Self::Output {
+        find_first_synchronized_step(&mut grid.clone())
+    }
+}
+
+const FLASHED: i8 = -1;
+
+pub fn build_grid(input: &str) -> Result<Grid<i8>> {
+    parse::digit_grid(input)
+}
+
+pub fn read_grid_line(line: &str) -> Result<Vec<i8>> {
+    // parse "5483143223"
+    parse::digit_line(line)
+}
+
+pub fn step(grid: &mut Grid<i8>) -> usize {
+    part_one(grid);
+    part_two(grid);
+    part_three(grid);
+
+    grid.iter().filter(|energy| **energy == 0).count()
+}
+
+pub fn find_first_synchronized_step(grid: &mut Grid<i8>) -> usize {
+    let num_octopuses = grid.len();
+    let mut step_num = 0;
+
+    loop {
+        step_num += 1;
+        if step(grid) == num_octopuses {
+            return step_num;
+        }
+    }
+}
+
+fn part_one(grid: &mut Grid<i8>) {
+    // Increase energy level of each by 1
+    for energy in grid.iter_mut() {
+        *energy += 1;
+    }
+}
+
+fn part_two(grid: &mut Grid<i8>) {
+    // Any octopus with an energy level greater than 9 flashes .
+    // This increases the energy level of all adjacent octopuses by 1,
+    // including octopuses that are diagonally adjacent.
+    // If this causes an octopus to have an energy level greater than 9, it also flashes.
+    // This process continues as long as new octopuses keep having their energy level increased beyond 9.
+    // (An octopus can only flash at most once per step.)
+
+    let mut octopus_flashed = true;
+
+    while octopus_flashed {
+        octopus_flashed = false;
+
+        for (row, col) in grid.positions() {
+            if grid[(row, col)] > 9 {
+                octopus_flashed = true;
+                grid[(row, col)] = FLASHED;
+                increase_adjacent_energy_levels(grid, row, col);
+            }
+        }
+    }
+}
+
+fn increase_adjacent_energy_levels(grid: &mut Grid<i8>, row: usize, col: usize) {
+    // all eight neighbors, including diagonals
+    for position in grid.neighbors8(row, col) {
+        if grid[position] != FLASHED {
+            grid[position] += 1;
+        }
+    }
+}
+
+fn part_three(grid: &mut Grid<i8>) {
+    // Any octopus that flashed during this step has its energy level set to 0,
+    // as it used all of its energy to flash.
+    for energy in grid.iter_mut() {
+        if *energy == FLASHED {
+            *energy = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod day11_tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_build_grid() {
+        let input = fs::read_to_string("./data/sample11_small.txt").unwrap();
+
+        let grid = build_grid(&input).unwrap();
+        assert_eq!(
+            "11111\n\
+             19991\n\
+             19191\n\
+             19991\n\
+             11111",
+            grid.to_string()
+        );
+    }
+
+    #[test]
+    fn test_read_grid_line() {
+        assert_eq!(vec![5, 4, 8, 3], read_grid_line("5483").unwrap());
+
+        let err = read_grid_line("54-3").unwrap_err();
+        assert_eq!("1:3: expected a digit, found `-`", err.to_string());
+    }
+
+    #[test]
+    fn test_step() {
+        let input = fs::read_to_string("./data/sample11_small.txt").unwrap();
+
+        let mut grid = build_grid(&input).unwrap();
+        assert_eq!(
+            "11111\n\
+             19991\n\
+             19191\n\
+             19991\n\
+             11111",
+            grid.to_string()
+        );
+
+        step(&mut grid);
+        assert_eq!(
+            "34543\n\
+             40004\n\
+             50005\n\
+             40004\n\
+             34543",
+            grid.to_string()
+        );
+
+        step(&mut grid);
+        assert_eq!(
+            "45654\n\
+             51115\n\
+             61116\n\
+             51115\n\
+             45654",
+            grid.to_string()
+        );
+    }
+
+    #[test]
+    fn test_count_flashes() {
+        let input = fs::read_to_string("./data/sample11.txt").unwrap();
+
+        let mut grid = build_grid(&input).unwrap();
+        assert_eq!(
+            "5483143223\n\
+             2745854711\n\
+             5264556173\n\
+             6141336146\n\
+             6357385478\n\
+             4167524645\n\
+             2176841721\n\
+             6882881134\n\
+             4846848554\n\
+             5283751526",
+            grid.to_string()
+        );
+
+        let mut total_flashes = 0;
+        for _ in 0..10 {
+            total_flashes += step(&mut grid);
+        }
+
+        assert_eq!(204, total_flashes);
+
+        assert_eq!(
+            "0481112976\n\
+             0031112009\n\
+             0041112504\n\
+             0081111406\n\
+             0099111306\n\
+             0093511233\n\
+             0442361130\n\
+             5532252350\n\
+             0532250600\n\
+             0032240000",
+            grid.to_string()
+        );
+
+        for _ in 0..90 {
+            total_flashes += step(&mut grid);
+        }
+
+        assert_eq!(1656, total_flashes);
+
+        assert_eq!(
+            "0397666866\n\
+             0749766918\n\
+             0053976933\n\
+             0004297822\n\
+             0004229892\n\
+             0053222877\n\
+             0532222966\n\
+             9322228966\n\
+             7922286866\n\
+             6789998766",
+            grid.to_string()
+        );
+    }
+}
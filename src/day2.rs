@@ -0,0 +1,185 @@
+use crate::{
+    error::{next_token, parse_token},
+    Day, ParseError, Result,
+};
+
+pub struct Day2;
+
+impl Day for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Command>;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_planned_course(input)
+    }
+
+    fn part1(planned_course: &Self::Input) -> Self::Output {
+        let (position, depth) = calculate_position_and_depth_without_aim(planned_course);
+        position * depth
+    }
+
+    fn part2(planned_course: &Self::Input) -> Self::Output {
+        let (position, depth) = calculate_position_and_depth(planned_course);
+        position * depth
+    }
+}
+
+/// One step of the planned course, with its distance.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Command {
+    Forward(u32),
+    Down(u32),
+    Up(u32),
+}
+
+pub fn parse_planned_course(input: &str) -> Result<Vec<Command>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| read_course(line).map_err(|err| err.offset_lines(i)))
+        .collect()
+}
+
+fn read_course(line: &str) -> Result<Command> {
+    // parse "forward 5"
+    let mut parts = line.split(' ');
+
+    let direction = next_token(line, parts.next(), "a direction")?;
+    let command = match direction {
+        "forward" => Command::Forward,
+        "down" => Command::Down,
+        "up" => Command::Up,
+        _ => {
+            return Err(ParseError::invalid_token(
+                line,
+                direction,
+                "'forward', 'up' or 'down'",
+            ))
+        }
+    };
+
+    let value = next_token(line, parts.next(), "a distance")?;
+    let distance = parse_token::<u32>(line, value, "a distance")?;
+
+    if let Some(extra) = parts.next() {
+        return Err(ParseError::invalid_token(line, extra, "end of line"));
+    }
+
+    Ok(command(distance))
+}
+
+pub fn calculate_position_and_depth_without_aim(planned_course: &[Command]) -> (u32, u32) {
+    let mut forward_position: u32 = 0;
+    let mut depth: u32 = 0;
+
+    for command in planned_course {
+        match *command {
+            Command::Forward(value) => forward_position += value,
+            Command::Up(value) => depth -= value,
+            Command::Down(value) => depth += value,
+        }
+    }
+
+    (forward_position, depth)
+}
+
+pub fn calculate_position_and_depth(planned_course: &[Command]) -> (u32, u32) {
+    let mut forward_position: u32 = 0;
+    let mut depth: u32 = 0;
+    let mut aim: u32 = 0;
+
+    for command in planned_course {
+        match *command {
+            Command::Forward(forward) => {
+                forward_position += forward;
+                depth += forward * aim;
+            }
+            Command::Up(value) => aim -= value,
+            Command::Down(value) => aim += value,
+        }
+    }
+
+    (forward_position, depth)
+}
+
+#[cfg(test)]
+mod day2_tests {
+    use super::*;
+    mod test_parse_planned_course {
+        use super::*;
+
+        #[test]
+        fn sample() {
+            let planned_course = parse_planned_course("forward 5\ndown 5\nup 3").unwrap();
+            assert_eq!(
+                planned_course,
+                vec![Command::Forward(5), Command::Down(5), Command::Up(3)]
+            );
+        }
+
+        #[test]
+        fn invalid_direction() {
+            let err = parse_planned_course("forward 5\nbackward 5").unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "2:1: expected 'forward', 'up' or 'down', found `backward`"
+            );
+        }
+
+        #[test]
+        fn extra_token() {
+            let err = parse_planned_course("forward 5\nforward 5 junk").unwrap_err();
+            assert_eq!(err.to_string(), "2:11: expected end of line, found `junk`");
+        }
+
+        #[test]
+        fn missing_distance() {
+            let err = parse_planned_course("forward 5\ndown").unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "2:5: expected a distance, found end of line"
+            );
+        }
+    }
+
+    mod test_calculate_position_and_depth_without_aim {
+        use super::*;
+
+        #[test]
+        fn sample() {
+            let planned_course = vec![
+                Command::Forward(5),
+                Command::Down(5),
+                Command::Forward(8),
+                Command::Up(3),
+                Command::Down(8),
+                Command::Forward(2),
+            ];
+
+            assert_eq!(
+                calculate_position_and_depth_without_aim(&planned_course),
+                (15, 10)
+            );
+        }
+    }
+
+    mod test_calculate_position_and_depth {
+        use super::*;
+
+        #[test]
+        fn sample() {
+            let planned_course = vec![
+                Command::Forward(5),
+                Command::Down(5),
+                Command::Forward(8),
+                Command::Up(3),
+                Command::Down(8),
+                Command::Forward(2),
+            ];
+
+            assert_eq!(calculate_position_and_depth(&planned_course), (15, 60));
+        }
+    }
+}
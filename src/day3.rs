@@ -0,0 +1,263 @@
+use crate::{Day, ParseError, Result};
+
+pub struct Day3;
+
+impl Day for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<String>;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_report(input)
+    }
+
+    fn part1(report: &Self::Input) -> Self::Output {
+        let (gamma, epsilon) = calculate_gamma_and_epsilon(report);
+        gamma * epsilon
+    }
+
+    fn part2(report: &Self::Input) -> Self::Output {
+        let oxygen_generator_rating = calculate_oxygen_generator_rating(report.clone());
+        let co2_scrubber_rating = calculate_co2_scrubber_rating(report.clone());
+        oxygen_generator_rating * co2_scrubber_rating
+    }
+}
+
+pub fn parse_report(input: &str) -> Result<Vec<String>> {
+    let mut report: Vec<String> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        // check "00100"
+        if report.is_empty() && line.is_empty() {
+            return Err(ParseError::unexpected_end(line, "a binary number").offset_lines(i));
+        }
+        if let Some(pos) = line.find(|chr| chr != '0' && chr != '1') {
+            let bit = &line[pos..pos + line[pos..].chars().next().unwrap().len_utf8()];
+            return Err(ParseError::invalid_token(line, bit, "a binary digit").offset_lines(i));
+        }
+
+        if let Some(first) = report.first() {
+            let expected = format!("{} binary digits", first.len());
+            if line.len() < first.len() {
+                return Err(ParseError::unexpected_end(line, &expected).offset_lines(i));
+            } else if line.len() > first.len() {
+                let extra = &line[first.len()..];
+                return Err(ParseError::invalid_token(line, extra, &expected).offset_lines(i));
+            }
+        }
+
+        report.push(line.to_owned());
+    }
+
+    if report.is_empty() {
+        return Err(ParseError::unexpected_end(input, "a binary number"));
+    }
+
+    Ok(report)
+}
+
+pub fn calculate_gamma_and_epsilon(report: &[String]) -> (u32, u32) {
+    let mut gamma = String::new();
+    let mut epsilon = String::new();
+
+    // Assume all binary numbers in report are the same length
+    let length = &report[0].len();
+
+    for i in 0..*length {
+        let num_zeroes = report
+            .iter()
+            .filter(|num| num.as_bytes()[i] == b'0')
+            .count();
+        if num_zeroes > report.len() / 2 {
+            gamma += "0";
+            epsilon += "1";
+        } else {
+            gamma += "1";
+            epsilon += "0";
+        }
+    }
+
+    let gamma: u32 = u32::from_str_radix(&gamma, 2).unwrap();
+    let epsilon: u32 = u32::from_str_radix(&epsilon, 2).unwrap();
+
+    (gamma, epsilon)
+}
+
+pub fn calculate_oxygen_generator_rating(mut report: Vec<String>) -> u32 {
+    // Assume all binary numbers in report are the same length
+    let length = &report[0].len();
+
+    for i in 0..*length {
+        if report.len() == 1 {
+            let oxygen_generator_rating: u32 = u32::from_str_radix(&report[0], 2).unwrap();
+            return oxygen_generator_rating;
+        }
+
+        let num_zeroes = report
+            .iter()
+            .filter(|num| num.as_bytes()[i] == b'0')
+            .count();
+        if num_zeroes > report.len() / 2 {
+            // Keep only numbers with a '0' at the ith position
+            report.retain(|num| num.as_bytes()[i] == b'0');
+        } else {
+            // Keep only numbers with a '1' at the ith position
+            report.retain(|num| num.as_bytes()[i] == b'1');
+        }
+    }
+
+    if report.len() == 1 {
+        let oxygen_generator_rating: u32 = u32::from_str_radix(&report[0], 2).unwrap();
+        return oxygen_generator_rating;
+    }
+
+    0
+}
+
+pub fn calculate_co2_scrubber_rating(mut report: Vec<String>) -> u32 {
+    // Assume all binary numbers in report are the same length
+    let length = &report[0].len();
+
+    for i in 0..*length {
+        if report.len() == 1 {
+            let co2_scrubber_rating: u32 = u32::from_str_radix(&report[0], 2).unwrap();
+            return co2_scrubber_rating;
+        }
+
+        let num_zeroes = report
+            .iter()
+            .filter(|num| num.as_bytes()[i] == b'0')
+            .count();
+        if num_zeroes > report.len() / 2 {
+            // Keep only numbers with a '1' at the ith position
+            report.retain(|num| num.as_bytes()[i] == b'1');
+        } else {
+            // Keep only numbers with a '0' at the ith position
+            report.retain(|num| num.as_bytes()[i] == b'0');
+        }
+    }
+
+    if report.len() == 1 {
+        let co2_scrubber_rating: u32 = u32::from_str_radix(&report[0], 2).unwrap();
+        return co2_scrubber_rating;
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod day3_tests {
+    use super::*;
+    mod test_parse_report {
+        use super::*;
+
+        #[test]
+        fn sample() {
+            let report = parse_report("00100\n11110\n10110").unwrap();
+            assert_eq!(report, vec!["00100", "11110", "10110"]);
+        }
+
+        #[test]
+        fn invalid_digit() {
+            let err = parse_report("00100\n11210").unwrap_err();
+            assert_eq!(err.to_string(), "2:3: expected a binary digit, found `2`");
+        }
+
+        #[test]
+        fn empty() {
+            let err = parse_report("").unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "1:1: expected a binary number, found end of line"
+            );
+
+            let err = parse_report("\n00100").unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "1:1: expected a binary number, found end of line"
+            );
+        }
+
+        #[test]
+        fn different_lengths() {
+            let err = parse_report("00100\n1111").unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "2:5: expected 5 binary digits, found end of line"
+            );
+        }
+    }
+
+    mod test_calculate_gamma_and_epsilon {
+        use super::*;
+
+        #[test]
+        fn sample() {
+            let report = vec![
+                "00100".to_owned(),
+                "11110".to_owned(),
+                "10110".to_owned(),
+                "10111".to_owned(),
+                "10101".to_owned(),
+                "01111".to_owned(),
+                "00111".to_owned(),
+                "11100".to_owned(),
+                "10000".to_owned(),
+                "11001".to_owned(),
+                "00010".to_owned(),
+                "01010".to_owned(),
+            ];
+
+            assert_eq!(calculate_gamma_and_epsilon(&report), (22, 9));
+        }
+    }
+
+    mod test_calculate_oxygen_generator_rating {
+        use super::*;
+
+        #[test]
+        fn sample() {
+            let report = vec![
+                "00100".to_owned(),
+                "11110".to_owned(),
+                "10110".to_owned(),
+                "10111".to_owned(),
+                "10101".to_owned(),
+                "01111".to_owned(),
+                "00111".to_owned(),
+                "11100".to_owned(),
+                "10000".to_owned(),
+                "11001".to_owned(),
+                "00010".to_owned(),
+                "01010".to_owned(),
+            ];
+
+            assert_eq!(calculate_oxygen_generator_rating(report.clone()), 23);
+        }
+    }
+
+    mod test_calculate_co2_scrubber_rating {
+        use super::*;
+
+        #[test]
+        fn sample() {
+            let report = vec![
+                "00100".to_owned(),
+                "11110".to_owned(),
+                "10110".to_owned(),
+                "10111".to_owned(),
+                "10101".to_owned(),
+                "01111".to_owned(),
+                "00111".to_owned(),
+                "11100".to_owned(),
+                "10000".to_owned(),
+                "11001".to_owned(),
+                "00010".to_owned(),
+                "01010".to_owned(),
+            ];
+
+            assert_eq!(calculate_co2_scrubber_rating(report.clone()), 10);
+        }
+    }
+}
@@ -0,0 +1,1231 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+    ops::Index,
+    str::Lines,
+};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    error::{next_token, parse_token},
+    Day, Fallible, Grid, ParseError, Result,
+};
+
+pub struct Day4;
+
+impl Day for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = (Vec<u32>, Vec<BingoBoard>);
+    type Output = Fallible<u32, NoWinningBoard>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_bingo_game(input)
+    }
+
+    fn part1((draw, boards): &Self::Input) -> Self::Output {
+        let mut game = BingoGame::new(boards.clone());
+
+        let win = game.wins(draw).next();
+
+        Fallible(win.map(|win| win.score).ok_or(NoWinningBoard))
+    }
+
+    fn part2((draw, boards): &Self::Input) -> Self::Output {
+        let mut game = BingoGame::new(boards.clone());
+
+        let win = game.wins(draw).last();
+
+        Fallible(win.map(|win| win.score).ok_or(NoWinningBoard))
+    }
+}
+
+/// The draw runs out before any board completes a line.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct NoWinningBoard;
+
+impl fmt::Display for NoWinningBoard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no winning board")
+    }
+}
+
+impl Error for NoWinningBoard {}
+
+#[derive(Copy, Clone)]
+pub struct BingoNumber {
+    pub value: u32,
+    pub marked: bool,
+}
+
+impl BingoNumber {
+    pub fn new(value: u32) -> Self {
+        Self {
+            value,
+            marked: false,
+        }
+    }
+}
+
+/// Which lines of marked numbers count as a bingo.
+///
+/// Diagonals only exist on square boards.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BingoRules {
+    pub rows: bool,
+    pub columns: bool,
+    pub diagonals: bool,
+    pub corners: bool,
+    pub full_card: bool,
+}
+
+impl BingoRules {
+    /// Any complete row or column, as in the puzzle.
+    pub const STANDARD: Self = Self {
+        rows: true,
+        columns: true,
+        diagonals: false,
+        corners: false,
+        full_card: false,
+    };
+}
+
+impl Default for BingoRules {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+#[derive(Clone)]
+pub struct BingoBoard {
+    board: Grid<BingoNumber>,
+    rules: BingoRules,
+}
+
+impl BingoBoard {
+    /// Builds a board from its numbers in row-major order.
+    pub fn new(width: usize, height: usize, values: Vec<u32>) -> Self {
+        Self {
+            board: Grid::from_vec(
+                width,
+                height,
+                values.into_iter().map(BingoNumber::new).collect(),
+            ),
+            rules: BingoRules::default(),
+        }
+    }
+
+    /// Reads rows of whitespace separated numbers up to the next blank line.
+    /// The first row sets the width of the board.
+    pub fn from_lines(numbers: &mut Lines) -> Result<Self> {
+        let mut width = 0;
+        let mut cells = Vec::new();
+        let mut height = 0;
+
+        for (i, row_str) in numbers
+            .take_while(|line| !line.trim().is_empty())
+            .enumerate()
+        {
+            let mut values = row_str.split_ascii_whitespace();
+
+            if i == 0 {
+                for value in values {
+                    let value = parse_token(row_str, value, "a number")?;
+                    cells.push(BingoNumber::new(value));
+                }
+                width = cells.len();
+            } else {
+                for _ in 0..width {
+                    let value = next_token(row_str, values.next(), "a number")
+                        .and_then(|value| parse_token(row_str, value, "a number"))
+                        .map_err(|err| err.offset_lines(i))?;
+                    cells.push(BingoNumber::new(value));
+                }
+
+                if let Some(extra) = values.next() {
+                    return Err(
+                        ParseError::invalid_token(row_str, extra, "the end of the row")
+                            .offset_lines(i),
+                    );
+                }
+            }
+
+            height += 1;
+        }
+
+        if height == 0 {
+            return Err(ParseError::unexpected_end("", "a row of numbers"));
+        }
+
+        Ok(Self {
+            board: Grid::from_vec(width, height, cells),
+            rules: BingoRules::default(),
+        })
+    }
+
+    pub fn with_rules(mut self, rules: BingoRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn rules(&self) -> BingoRules {
+        self.rules
+    }
+
+    pub fn width(&self) -> usize {
+        self.board.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.board.height()
+    }
+
+    pub fn mark(&mut self, value: u32) {
+        for num in self.board.iter_mut() {
+            if num.value == value {
+                num.marked = true;
+            }
+        }
+    }
+
+    pub fn has_bingo(&self) -> bool {
+        let marked = |&(row, col): &(usize, usize)| self.board[(row, col)].marked;
+
+        if self.rules.rows
+            && self
+                .board
+                .rows()
+                .any(|row| row.iter().all(|num| num.marked))
+        {
+            return true;
+        }
+
+        if self.rules.columns
+            && (0..self.width()).any(|col| self.board.column(col).all(|num| num.marked))
+        {
+            return true;
+        }
+
+        if self.rules.diagonals
+            && self
+                .diagonals()
+                .iter()
+                .any(|diagonal| diagonal.iter().all(marked))
+        {
+            return true;
+        }
+
+        if self.rules.corners && self.corners().iter().all(marked) {
+            return true;
+        }
+
+        self.rules.full_card && self.board.iter().all(|num| num.marked)
+    }
+
+    pub fn sum_unmarked(&self) -> u32 {
+        self.board
+            .iter()
+            .filter(|&num| !num.marked)
+            .map(|&num| num.value)
+            .sum()
+    }
+
+    /// The numbers of every line that wins under the board's rules.
+    pub fn winning_lines(&self) -> Vec<Vec<u32>> {
+        let value = |&(row, col): &(usize, usize)| self.board[(row, col)].value;
+        let mut lines = Vec::new();
+
+        if self.rules.rows {
+            lines.extend(
+                self.board
+                    .rows()
+                    .map(|row| row.iter().map(|num| num.value).collect()),
+            );
+        }
+        if self.rules.columns {
+            lines.extend(
+                (0..self.width()).map(|col| self.board.column(col).map(|num| num.value).collect()),
+            );
+        }
+        if self.rules.diagonals {
+            lines.extend(
+                self.diagonals()
+                    .iter()
+                    .map(|diagonal| diagonal.iter().map(value).collect()),
+            );
+        }
+        if self.rules.corners {
+            lines.push(self.corners().iter().map(value).collect());
+        }
+        if self.rules.full_card {
+            lines.push(self.board.iter().map(|num| num.value).collect());
+        }
+
+        lines
+    }
+
+    // both diagonals of a square board, none otherwise
+    fn diagonals(&self) -> Vec<Vec<(usize, usize)>> {
+        let size = self.width();
+        if size != self.height() {
+            return Vec::new();
+        }
+
+        vec![
+            (0..size).map(|i| (i, i)).collect(),
+            (0..size).map(|i| (i, size - 1 - i)).collect(),
+        ]
+    }
+
+    // distinct corner positions, fewer than four on a single row or column
+    fn corners(&self) -> Vec<(usize, usize)> {
+        let (last_row, last_col) = (self.height() - 1, self.width() - 1);
+        let mut corners = vec![(0, 0), (0, last_col), (last_row, 0), (last_row, last_col)];
+        corners.sort_unstable();
+        corners.dedup();
+
+        corners
+    }
+}
+
+impl Index<usize> for BingoBoard {
+    type Output = [BingoNumber];
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.board.row(index)
+    }
+}
+
+impl fmt::Display for BingoBoard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let max = self.board.iter().map(|num| num.value).max().unwrap_or(0);
+        let width = max.to_string().len().max(2);
+
+        let rows: Vec<String> = self
+            .board
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|num| format!("{:>width$}", num.value, width = width))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect();
+
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+pub fn parse_bingo_game(input: &str) -> Result<(Vec<u32>, Vec<BingoBoard>)> {
+    let mut lines = input.lines();
+    let draw = parse_bingo_draw(next_token(input, lines.next(), "the bingo draw")?)?;
+
+    let mut boards = Vec::new();
+    let mut lines_read = 1;
+
+    // blank line after the draw
+    if let Some(line) = lines.next() {
+        if let Some(token) = line.split_whitespace().next() {
+            return Err(ParseError::invalid_token(line, token, "a blank line").offset_lines(1));
+        }
+        lines_read += 1;
+    }
+
+    while lines.clone().next().is_some() {
+        let board =
+            BingoBoard::from_lines(&mut lines).map_err(|err| err.offset_lines(lines_read))?;
+        lines_read += board.height() + 1;
+        boards.push(board);
+    }
+
+    Ok((draw, boards))
+}
+
+/// A board completing a winning line, in the order the boards win.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Win {
+    pub board_index: usize,
+    pub number: u32,
+    pub score: u32,
+}
+
+/// Plays a set of boards against a draw without rescanning them.
+///
+/// Every number is indexed to the cells holding it, and each board keeps a
+/// count of marks per row, column, diagonal and corner, so marking a cell and
+/// checking for bingo are both O(1).
+pub struct BingoGame {
+    boards: Vec<BingoBoard>,
+    // number -> (board, row, col) of every cell holding it
+    cells: HashMap<u32, Vec<(usize, usize, usize)>>,
+    tallies: Vec<Tally>,
+    unmarked: Vec<u32>,
+    won: Vec<bool>,
+}
+
+impl BingoGame {
+    pub fn new(boards: Vec<BingoBoard>) -> Self {
+        let mut cells: HashMap<u32, Vec<_>> = HashMap::new();
+        let mut tallies = Vec::with_capacity(boards.len());
+
+        for (index, board) in boards.iter().enumerate() {
+            let mut tally = Tally::new(board);
+
+            for (row, col) in board.board.positions() {
+                let num = board.board[(row, col)];
+                cells.entry(num.value).or_default().push((index, row, col));
+
+                // boards may arrive with cells already marked
+                if num.marked {
+                    tally.mark(board, row, col);
+                }
+            }
+
+            tallies.push(tally);
+        }
+
+        Self {
+            cells,
+            tallies,
+            unmarked: boards.iter().map(|board| board.sum_unmarked()).collect(),
+            won: vec![false; boards.len()],
+            boards,
+        }
+    }
+
+    pub fn boards(&self) -> &[BingoBoard] {
+        &self.boards
+    }
+
+    /// Marks `number` on every board and returns the boards that won because of it.
+    pub fn call(&mut self, number: u32) -> Vec<Win> {
+        let mut wins = Vec::new();
+        let Self {
+            boards,
+            cells,
+            tallies,
+            unmarked,
+            won,
+        } = self;
+
+        for &(index, row, col) in cells.get(&number).into_iter().flatten() {
+            let cell = &mut boards[index].board[(row, col)];
+            if cell.marked {
+                continue;
+            }
+            cell.marked = true;
+            unmarked[index] -= number;
+
+            let bingo = tallies[index].mark(&boards[index], row, col);
+            if bingo && !won[index] {
+                won[index] = true;
+                wins.push(Win {
+                    board_index: index,
+                    number,
+                    score: unmarked[index] * number,
+                });
+            }
+        }
+
+        wins
+    }
+
+    /// Calls each number of `draw` in turn, yielding wins as they happen.
+    pub fn wins<'a>(&'a mut self, draw: &'a [u32]) -> impl Iterator<Item = Win> + 'a {
+        draw.iter().flat_map(move |&number| self.call(number))
+    }
+}
+
+// Marks counted per winning line of a single board
+struct Tally {
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+    diagonals: [usize; 2],
+    corners: usize,
+    corner_cells: Vec<(usize, usize)>,
+    total: usize,
+}
+
+impl Tally {
+    fn new(board: &BingoBoard) -> Self {
+        Self {
+            rows: vec![0; board.height()],
+            columns: vec![0; board.width()],
+            diagonals: [0; 2],
+            corners: 0,
+            corner_cells: board.corners(),
+            total: 0,
+        }
+    }
+
+    // counts a newly marked cell, returning true if the board now has bingo
+    fn mark(&mut self, board: &BingoBoard, row: usize, col: usize) -> bool {
+        let (width, height) = (board.width(), board.height());
+        let rules = board.rules();
+
+        self.rows[row] += 1;
+        self.columns[col] += 1;
+        self.total += 1;
+
+        let square = width == height;
+        let on_diagonal = square && row == col;
+        let on_anti_diagonal = square && row + col == width - 1;
+        if on_diagonal {
+            self.diagonals[0] += 1;
+        }
+        if on_anti_diagonal {
+            self.diagonals[1] += 1;
+        }
+
+        if self.corner_cells.contains(&(row, col)) {
+            self.corners += 1;
+        }
+
+        (rules.rows && self.rows[row] == width)
+            || (rules.columns && self.columns[col] == height)
+            || (rules.diagonals && on_diagonal && self.diagonals[0] == width)
+            || (rules.diagonals && on_anti_diagonal && self.diagonals[1] == width)
+            || (rules.corners && self.corners == self.corner_cells.len())
+            || (rules.full_card && self.total == width * height)
+    }
+}
+
+pub fn parse_bingo_draw(input: &str) -> Result<Vec<u32>> {
+    input
+        .split(',')
+        .map(|num| parse_token(input, num, "a number"))
+        .collect()
+}
+
+/// Writes a draw and boards in the puzzle input format.
+pub fn format_bingo_game(draw: &[u32], boards: &[BingoBoard]) -> String {
+    let draw = draw
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let mut input = draw;
+    for board in boards {
+        input.push_str("\n\n");
+        input.push_str(&board.to_string());
+    }
+    input.push('\n');
+
+    input
+}
+
+/// Settings for generating random puzzle inputs.
+///
+/// The draw is a shuffle of every number below `numbers`, and each board holds
+/// distinct numbers from that range.
+#[derive(Copy, Clone, Debug)]
+pub struct BingoGenerator {
+    pub boards: usize,
+    pub width: usize,
+    pub height: usize,
+    pub numbers: u32,
+}
+
+impl Default for BingoGenerator {
+    /// The shape of the real puzzle inputs.
+    fn default() -> Self {
+        Self {
+            boards: 100,
+            width: 5,
+            height: 5,
+            numbers: 100,
+        }
+    }
+}
+
+impl BingoGenerator {
+    /// Panics if a board has more cells than there are numbers to fill it.
+    pub fn generate<R: Rng>(&self, rng: &mut R) -> (Vec<u32>, Vec<BingoBoard>) {
+        let cells = self.width * self.height;
+        assert!(
+            cells <= self.numbers as usize,
+            "a {}x{} board needs at least {} numbers",
+            self.width,
+            self.height,
+            cells
+        );
+
+        let mut draw: Vec<u32> = (0..self.numbers).collect();
+        draw.shuffle(rng);
+
+        let boards = (0..self.boards)
+            .map(|_| {
+                let values = rand::seq::index::sample(rng, self.numbers as usize, cells)
+                    .into_iter()
+                    .map(|value| value as u32)
+                    .collect();
+                BingoBoard::new(self.width, self.height, values)
+            })
+            .collect();
+
+        (draw, boards)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Finish {
+    First,
+    Last,
+}
+
+/// Reorders `numbers` so that the board at `target` is the first or the last
+/// board to win, or returns `None` if no order of `numbers` can do that.
+///
+/// A board can only win on numbers in `numbers`. Finding a last-place order
+/// is greedy, so it can give up on a set of boards that does have one.
+pub fn rig_draw(
+    numbers: &[u32],
+    boards: &[BingoBoard],
+    target: usize,
+    finish: Finish,
+) -> Option<Vec<u32>> {
+    let available: HashSet<u32> = numbers.iter().copied().collect();
+    let drawable = |line: &Vec<u32>| line.iter().all(|number| available.contains(number));
+
+    let mut called: Vec<u32> = Vec::new();
+    let mut called_set: HashSet<u32> = HashSet::new();
+
+    match finish {
+        Finish::First => {
+            // call one of the target's lines that gives no other board a bingo
+            let line = boards[target]
+                .winning_lines()
+                .into_iter()
+                .filter(drawable)
+                .find(|line| {
+                    let line: HashSet<u32> = line.iter().copied().collect();
+                    boards
+                        .iter()
+                        .enumerate()
+                        .all(|(i, board)| i == target || !wins_with(board, &line))
+                })?;
+
+            for number in line {
+                if called_set.insert(number) {
+                    called.push(number);
+                }
+            }
+        }
+        Finish::Last => {
+            if !wins_with(&boards[target], &available) {
+                return None;
+            }
+
+            // give every other board a bingo, cheapest line first, without completing the target
+            for (i, board) in boards.iter().enumerate() {
+                if i == target || wins_with(board, &called_set) {
+                    continue;
+                }
+
+                let line = board
+                    .winning_lines()
+                    .into_iter()
+                    .filter(drawable)
+                    .filter(|line| {
+                        let mut with_line = called_set.clone();
+                        with_line.extend(line);
+                        !wins_with(&boards[target], &with_line)
+                    })
+                    .min_by_key(|line| {
+                        line.iter()
+                            .filter(|number| !called_set.contains(number))
+                            .count()
+                    })?;
+
+                for number in line {
+                    if called_set.insert(number) {
+                        called.push(number);
+                    }
+                }
+            }
+        }
+    }
+
+    called.extend(numbers.iter().filter(|number| !called_set.contains(number)));
+
+    Some(called)
+}
+
+// whether the board has bingo once every number in `called` is marked
+fn wins_with(board: &BingoBoard, called: &HashSet<u32>) -> bool {
+    let mut board = board.clone();
+    for num in board.board.iter_mut() {
+        num.marked |= called.contains(&num.value);
+    }
+
+    board.has_bingo()
+}
+
+#[cfg(test)]
+mod day4_tests {
+    use super::*;
+
+    mod test_bingo_board {
+        use super::*;
+
+        #[test]
+        fn test_from_lines() {
+            fn row_to_string(row: &[BingoNumber]) -> String {
+                row.iter()
+                    .map(|&num| num.value.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            }
+
+            let lines = "22 13 17 11  0
+             8  2 23  4 24
+            21  9 14 16  7
+             6 10  3 18  5
+             1 12 20 15 19";
+
+            let board = BingoBoard::from_lines(&mut lines.lines()).unwrap();
+
+            assert_eq!(row_to_string(&board[0]), "22 13 17 11 0");
+            assert!(board[0].iter().all(|&num| !num.marked));
+
+            assert_eq!(row_to_string(&board[1]), "8 2 23 4 24");
+            assert!(board[1].iter().all(|&num| !num.marked));
+
+            assert_eq!(row_to_string(&board[2]), "21 9 14 16 7");
+            assert!(board[2].iter().all(|&num| !num.marked));
+
+            assert_eq!(row_to_string(&board[3]), "6 10 3 18 5");
+            assert!(board[3].iter().all(|&num| !num.marked));
+
+            assert_eq!(row_to_string(&board[4]), "1 12 20 15 19");
+            assert!(board[4].iter().all(|&num| !num.marked));
+        }
+
+        #[test]
+        fn test_mark() {
+            let lines = "22 13 17 11  0
+            8  2 23  4 24
+           21  9 14 16  7
+            6 10  3 18  5
+            1 12 20 15 19";
+
+            let mut board = BingoBoard::from_lines(&mut lines.lines()).unwrap();
+            board.mark(77);
+            board.mark(23);
+            board.mark(5);
+
+            assert!(board[1][2].marked);
+            assert!(!board[1][4].marked);
+            assert!(board[3][4].marked);
+        }
+
+        #[test]
+        fn test_sum_unmarked() {
+            let lines = "22 13 17 11  0
+            8  2 23  4 24
+           21  9 14 16  7
+            6 10  3 18  5
+            1 12 20 15 19";
+
+            let mut board = BingoBoard::from_lines(&mut lines.lines()).unwrap();
+            board.mark(22);
+            board.mark(13);
+            board.mark(17);
+            board.mark(23);
+            board.mark(24);
+            board.mark(21);
+            board.mark(9);
+            board.mark(14);
+            board.mark(16);
+            board.mark(10);
+            board.mark(3);
+            board.mark(18);
+            board.mark(12);
+            board.mark(20);
+            board.mark(15);
+            board.mark(19);
+
+            assert_eq!(board.sum_unmarked(), 44);
+        }
+
+        #[test]
+        fn test_has_bingo_row() {
+            let lines = "22 13 17 11  0
+            8  2 23  4 24
+           21  9 14 16  7
+            6 10  3 18  5
+            1 12 20 15 19";
+
+            let mut board = BingoBoard::from_lines(&mut lines.lines()).unwrap();
+
+            assert!(!board.has_bingo());
+            board.mark(1);
+            assert!(!board.has_bingo());
+            board.mark(12);
+            assert!(!board.has_bingo());
+            board.mark(20);
+            assert!(!board.has_bingo());
+            board.mark(15);
+            assert!(!board.has_bingo());
+            board.mark(19);
+            assert!(board.has_bingo());
+        }
+
+        #[test]
+        fn test_has_bingo_col() {
+            let lines = "22 13 17 11  0
+            8  2 23  4 24
+           21  9 14 16  7
+            6 10  3 18  5
+            1 12 20 15 19";
+
+            let mut board = BingoBoard::from_lines(&mut lines.lines()).unwrap();
+
+            assert!(!board.has_bingo());
+            board.mark(0);
+            assert!(!board.has_bingo());
+            board.mark(24);
+            assert!(!board.has_bingo());
+            board.mark(7);
+            assert!(!board.has_bingo());
+            board.mark(5);
+            assert!(!board.has_bingo());
+            board.mark(19);
+            assert!(board.has_bingo());
+        }
+    }
+
+    mod test_board_sizes_and_rules {
+        use super::*;
+
+        const SQUARE: &str = "1 2 3
+4 5 6
+7 8 9";
+
+        fn all_rules_off() -> BingoRules {
+            BingoRules {
+                rows: false,
+                columns: false,
+                diagonals: false,
+                corners: false,
+                full_card: false,
+            }
+        }
+
+        #[test]
+        fn test_from_lines_any_size() {
+            let mut lines = "1 2 3 4\n5 6 7 8\n\n9 10".lines();
+            let board = BingoBoard::from_lines(&mut lines).unwrap();
+
+            assert_eq!((board.width(), board.height()), (4, 2));
+            assert_eq!(board[1][3].value, 8);
+            // the blank line ends the board
+            assert_eq!(lines.next(), Some("9 10"));
+        }
+
+        #[test]
+        fn test_from_lines_long_row() {
+            let err = BingoBoard::from_lines(&mut "1 2\n3 4 5".lines())
+                .err()
+                .unwrap();
+            assert_eq!(
+                "2:5: expected the end of the row, found `5`",
+                err.to_string()
+            );
+        }
+
+        #[test]
+        fn test_parse_mixed_sizes() {
+            let (_, boards) = parse_bingo_game("1,2\n\n1 2\n3 4\n\n1 2 3\n4 5 6\n7 8 9").unwrap();
+            assert_eq!(boards.len(), 2);
+            assert_eq!(boards[1].height(), 3);
+
+            let err = parse_bingo_game("1,2\n\n1 2\n3 4\n\n1 2 3\n4 x 6")
+                .err()
+                .unwrap();
+            assert_eq!("7:3: expected a number, found `x`", err.to_string());
+        }
+
+        #[test]
+        fn test_diagonals() {
+            let board = BingoBoard::from_lines(&mut SQUARE.lines()).unwrap();
+            let mut board = board.with_rules(BingoRules {
+                diagonals: true,
+                ..all_rules_off()
+            });
+
+            board.mark(3);
+            board.mark(5);
+            assert!(!board.has_bingo());
+            board.mark(7);
+            assert!(board.has_bingo());
+        }
+
+        #[test]
+        fn test_corners() {
+            let board = BingoBoard::from_lines(&mut SQUARE.lines()).unwrap();
+            let mut board = board.with_rules(BingoRules {
+                corners: true,
+                ..all_rules_off()
+            });
+
+            for number in [1, 3, 7] {
+                board.mark(number);
+                assert!(!board.has_bingo());
+            }
+            board.mark(9);
+            assert!(board.has_bingo());
+        }
+
+        #[test]
+        fn test_full_card() {
+            let board = BingoBoard::from_lines(&mut SQUARE.lines()).unwrap();
+            let mut board = board.with_rules(BingoRules {
+                full_card: true,
+                ..all_rules_off()
+            });
+
+            for number in 1..9 {
+                board.mark(number);
+                assert!(!board.has_bingo());
+            }
+            board.mark(9);
+            assert!(board.has_bingo());
+        }
+
+        #[test]
+        fn test_rules_off() {
+            let board = BingoBoard::from_lines(&mut SQUARE.lines()).unwrap();
+            let mut board = board.with_rules(all_rules_off());
+
+            for number in 1..=9 {
+                board.mark(number);
+            }
+            assert!(!board.has_bingo());
+        }
+
+        #[test]
+        fn test_game_follows_rules() {
+            let rules = BingoRules {
+                diagonals: true,
+                corners: true,
+                ..BingoRules::STANDARD
+            };
+            let boards = ["1 2 3\n4 5 6\n7 8 9", "9 2 1\n4 5 6\n7 8 3\n10 11 12"]
+                .iter()
+                .map(|lines| {
+                    BingoBoard::from_lines(&mut lines.lines())
+                        .unwrap()
+                        .with_rules(rules)
+                })
+                .collect();
+            let mut game = BingoGame::new(boards);
+
+            let draw = [5, 9, 3, 1, 10, 12];
+            let wins: Vec<(usize, u32)> = game
+                .wins(&draw)
+                .map(|win| (win.board_index, win.number))
+                .collect();
+
+            // 9, 5, 3 is not a diagonal of the 4x3 board, only its corners win
+            assert_eq!(wins, vec![(0, 1), (1, 12)]);
+        }
+    }
+
+    mod test_bingo_game {
+        use super::*;
+
+        const SAMPLE: &str =
+            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+        #[test]
+        fn test_first_and_last_win() {
+            let (draw, boards) = parse_bingo_game(SAMPLE).unwrap();
+            let mut game = BingoGame::new(boards);
+            let wins: Vec<Win> = game.wins(&draw).collect();
+
+            assert_eq!(
+                wins,
+                vec![
+                    Win {
+                        board_index: 2,
+                        number: 24,
+                        score: 4512
+                    },
+                    Win {
+                        board_index: 0,
+                        number: 16,
+                        score: 137 * 16
+                    },
+                    Win {
+                        board_index: 1,
+                        number: 13,
+                        score: 1924
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn test_nth_win() {
+            let (draw, boards) = parse_bingo_game(SAMPLE).unwrap();
+            let mut game = BingoGame::new(boards);
+
+            assert_eq!(game.wins(&draw).nth(1).map(|win| win.board_index), Some(0));
+        }
+
+        #[test]
+        fn test_parts() {
+            let input = parse_bingo_game(SAMPLE).unwrap();
+            assert_eq!(Day4::part1(&input), Fallible(Ok(4512)));
+            assert_eq!(Day4::part2(&input), Fallible(Ok(1924)));
+        }
+
+        #[test]
+        fn test_no_winning_board() {
+            let input = parse_bingo_game("7,4,9\n\n1 2\n3 4").unwrap();
+            assert_eq!(Day4::part1(&input), Fallible(Err(NoWinningBoard)));
+            assert_eq!(Day4::part2(&input).to_string(), "error: no winning board");
+        }
+
+        #[test]
+        fn test_call() {
+            let (_, boards) = parse_bingo_game(SAMPLE).unwrap();
+            let mut game = BingoGame::new(boards);
+
+            for number in [1, 12, 20, 15] {
+                assert!(game.call(number).is_empty());
+            }
+            // calling a number twice doesn't count it twice
+            assert!(game.call(15).is_empty());
+
+            let wins = game.call(19);
+            assert_eq!(wins.len(), 1);
+            assert_eq!(wins[0].board_index, 0);
+            assert!(game.boards()[0].has_bingo());
+            assert!(game.boards()[1][0][1].marked);
+        }
+
+        #[test]
+        fn test_matches_has_bingo() {
+            let (draw, boards) = parse_bingo_game(SAMPLE).unwrap();
+            let mut game = BingoGame::new(boards);
+
+            for &number in &draw {
+                let winners: Vec<usize> = game
+                    .call(number)
+                    .iter()
+                    .map(|win| win.board_index)
+                    .collect();
+                for index in winners {
+                    assert!(game.boards()[index].has_bingo());
+                }
+            }
+        }
+    }
+
+    mod test_generator {
+        use super::*;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        fn winners(draw: &[u32], boards: &[BingoBoard]) -> Vec<usize> {
+            let mut game = BingoGame::new(boards.to_vec());
+            let winners = game.wins(draw).map(|win| win.board_index).collect();
+
+            winners
+        }
+
+        #[test]
+        fn test_generate_parses() {
+            let generator = BingoGenerator {
+                boards: 20,
+                ..BingoGenerator::default()
+            };
+            let (draw, boards) = generator.generate(&mut StdRng::seed_from_u64(4));
+
+            let mut sorted = draw.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..100).collect::<Vec<u32>>());
+
+            for board in &boards {
+                let values: HashSet<u32> = board.board.iter().map(|num| num.value).collect();
+                assert_eq!(values.len(), 25);
+            }
+
+            let input = format_bingo_game(&draw, &boards);
+            let (parsed_draw, parsed_boards) = parse_bingo_game(&input).unwrap();
+            assert_eq!(parsed_draw, draw);
+            assert_eq!(
+                parsed_boards
+                    .iter()
+                    .map(|board| board.to_string())
+                    .collect::<Vec<_>>(),
+                boards
+                    .iter()
+                    .map(|board| board.to_string())
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn test_generate_is_seeded() {
+            let generator = BingoGenerator::default();
+            let (draw1, boards1) = generator.generate(&mut StdRng::seed_from_u64(7));
+            let (draw2, boards2) = generator.generate(&mut StdRng::seed_from_u64(7));
+
+            assert_eq!(
+                format_bingo_game(&draw1, &boards1),
+                format_bingo_game(&draw2, &boards2)
+            );
+        }
+
+        #[test]
+        fn test_rig_first_and_last() {
+            let generator = BingoGenerator {
+                boards: 30,
+                ..BingoGenerator::default()
+            };
+
+            for seed in 0..10 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let (draw, boards) = generator.generate(&mut rng);
+                let target = rng.gen_range(0..boards.len());
+
+                let first = rig_draw(&draw, &boards, target, Finish::First).unwrap();
+                assert_eq!(winners(&first, &boards).first(), Some(&target));
+
+                let last = rig_draw(&draw, &boards, target, Finish::Last).unwrap();
+                let order = winners(&last, &boards);
+                assert_eq!(order.len(), boards.len());
+                assert_eq!(order.last(), Some(&target));
+
+                let mut sorted = last.clone();
+                sorted.sort_unstable();
+                assert_eq!(sorted, (0..100).collect::<Vec<u32>>());
+            }
+        }
+
+        #[test]
+        fn test_rig_impossible() {
+            let boards = vec![
+                BingoBoard::new(2, 2, vec![1, 2, 3, 4]),
+                BingoBoard::new(2, 2, vec![1, 2, 3, 4]),
+            ];
+
+            // identical boards always win together
+            assert_eq!(rig_draw(&[1, 2, 3, 4], &boards, 0, Finish::First), None);
+            assert_eq!(rig_draw(&[1, 2, 3, 4], &boards, 0, Finish::Last), None);
+            // 1 and 4 don't make a row or column
+            assert_eq!(rig_draw(&[1, 4], &boards[..1], 0, Finish::Last), None);
+        }
+    }
+
+    mod test_parse_bingo_game {
+        use super::*;
+
+        #[test]
+        fn test_short_row() {
+            let input = "7,4,9\n\n22 13 17 11  0\n 8  2 23  4\n21  9 14 16  7";
+            let err = parse_bingo_game(input).err().unwrap();
+
+            assert_eq!(
+                "4:12: expected a number, found end of line",
+                err.to_string()
+            );
+        }
+
+        #[test]
+        fn test_invalid_number_in_second_board() {
+            let input = "7,4,9
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 1O 24  4
+14 21 16 12  6";
+            let err = parse_bingo_game(input).err().unwrap();
+
+            assert_eq!("12:7: expected a number, found `1O`", err.to_string());
+        }
+
+        #[test]
+        fn test_missing_blank_line() {
+            let err = parse_bingo_game("1,2,3,4\n1 2\n3 4\n").err().unwrap();
+
+            assert_eq!("2:1: expected a blank line, found `1`", err.to_string());
+        }
+
+        #[test]
+        fn test_invalid_draw() {
+            let err = parse_bingo_draw("7,4,,5").unwrap_err();
+            assert_eq!("1:5: expected a number, found ``", err.to_string());
+        }
+    }
+
+    mod test_parse_bingo_draw {
+        use super::*;
+
+        #[test]
+        fn test_sample() {
+            let input = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+            22 13 17 11  0
+             8  2 23  4 24
+            21  9 14 16  7
+             6 10  3 18  5
+             1 12 20 15 19
+
+             3 15  0  2 22
+             9 18 13 17  5
+            19  8  7 25 23
+            20 11 10 24  4
+            14 21 16 12  6
+
+            14 21 17 24  4
+            10 16 15  9 19
+            18  8 23 26 20
+            22 11 13  6  5
+             2  0 12  3  7"
+                .to_owned();
+
+            let draw = parse_bingo_draw(input.lines().next().unwrap()).unwrap();
+            let string_list: String = draw
+                .iter()
+                .map(|&x| x.to_string())
+                .collect::<Vec<String>>()
+                .join(",");
+
+            assert_eq!(
+                string_list,
+                "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1"
+            );
+        }
+    }
+}
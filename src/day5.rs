@@ -0,0 +1,1312 @@
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+    io::{self, Write},
+};
+
+use crate::{
+    error::{next_token, parse_token},
+    Day, Grid, ParseError, Result,
+};
+
+pub struct Day5;
+
+impl Day for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Vec<LineSegment>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        read_line_segments(input)
+    }
+
+    fn part1(line_segments: &Self::Input) -> Self::Output {
+        count_overlapping_points(line_segments, false)
+    }
+
+    fn part2(line_segments: &Self::Input) -> Self::Output {
+        count_overlapping_points(line_segments, true)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LineSegment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl LineSegment {
+    pub fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    /// Any segment that is neither horizontal nor vertical.
+    pub fn is_diagonal(&self) -> bool {
+        !self.is_vertical() && !self.is_horizontal()
+    }
+
+    /// A diagonal at exactly 45 degrees, the only kind in the puzzle input.
+    pub fn is_45_degree(&self) -> bool {
+        self.is_diagonal() && self.start.x.abs_diff(self.end.x) == self.start.y.abs_diff(self.end.y)
+    }
+
+    /// The points the segment covers, drawn with Bresenham's algorithm if it
+    /// is not horizontal, vertical or at 45 degrees.
+    pub fn points(&self) -> Vec<Point> {
+        self.rasterise(Raster::Bresenham)
+    }
+
+    pub fn rasterise(&self, raster: Raster) -> Vec<Point> {
+        if self.is_vertical() {
+            let x = self.start.x;
+            let y = cmp::min(self.start.y, self.end.y);
+            let y_end = cmp::max(self.start.y, self.end.y);
+
+            return (y..=y_end).map(|y| Point::new(x, y)).collect();
+        } else if self.is_horizontal() {
+            let x = cmp::min(self.start.x, self.end.x);
+            let x_end = cmp::max(self.start.x, self.end.x);
+            let y = self.start.y;
+
+            return (x..=x_end).map(|x| Point::new(x, y)).collect();
+        }
+
+        match raster {
+            Raster::Bresenham => self.bresenham(),
+            Raster::LatticePoints => self.lattice_points(),
+        }
+    }
+
+    // integer Bresenham, walking from start to end in any octant, in i128 so
+    // that the differences and the doubled error can't overflow
+    fn bresenham(&self) -> Vec<Point> {
+        let (mut x, mut y) = (self.start.x as i128, self.start.y as i128);
+        let (x_end, y_end) = (self.end.x as i128, self.end.y as i128);
+
+        let dx = (x_end - x).abs();
+        let dy = -(y_end - y).abs();
+        let x_step = (x_end - x).signum();
+        let y_step = (y_end - y).signum();
+        let mut error = dx + dy;
+
+        let mut points = Vec::with_capacity(cmp::max(dx, -dy) as usize + 1);
+        loop {
+            // x and y stay between the ends, so they fit back in an i64
+            points.push(Point::new(x as i64, y as i64));
+            if x == x_end && y == y_end {
+                break;
+            }
+
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += x_step;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += y_step;
+            }
+        }
+
+        points
+    }
+
+    // only the points lying exactly on the segment, one step of (dx, dy) / gcd apart
+    fn lattice_points(&self) -> Vec<Point> {
+        let (x0, y0) = (self.start.x as i128, self.start.y as i128);
+        let dx = self.end.x as i128 - x0;
+        let dy = self.end.y as i128 - y0;
+        // both differences are at most u64::MAX
+        let steps = gcd(dx.unsigned_abs() as u64, dy.unsigned_abs() as u64) as i128;
+        let (x_step, y_step) = (dx / steps, dy / steps);
+
+        (0..=steps)
+            .map(|i| Point::new((x0 + i * x_step) as i64, (y0 + i * y_step) as i64))
+            .collect()
+    }
+
+    // whether every raster gives the segment the same points
+    fn is_lattice(&self) -> bool {
+        !self.is_diagonal() || self.is_45_degree()
+    }
+}
+
+/// How a segment that is not horizontal, vertical or at 45 degrees is turned into points.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Raster {
+    /// The closest point to the line in every column (or row, for steep lines).
+    #[default]
+    Bresenham,
+    /// Only the points the line passes through exactly.
+    LatticePoints,
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Where a `Diagram` keeps track of the lines covering each point.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Backend {
+    /// A count for every point up to the largest coordinates.
+    #[default]
+    Dense,
+    /// Counts for only the points some line covers.
+    Sparse,
+    /// No counts for horizontal, vertical and 45 degree segments: every pair
+    /// of them is intersected directly, taking time quadratic in the number
+    /// of segments but none in their length. Segments at other angles are
+    /// rasterised and their points checked against the rest, unless the
+    /// diagram uses `Raster::LatticePoints`, when they're intersected too.
+    Intersections,
+}
+
+impl Backend {
+    /// The largest number of points `Backend::Dense` is chosen to count.
+    pub const DENSE_LIMIT: u128 = 1 << 24;
+
+    /// The backend `count_overlapping_points` uses for `line_segments`: dense
+    /// for a small enough bounding box, otherwise intersections.
+    pub fn choose(line_segments: &[LineSegment]) -> Self {
+        let area = bounding_box(line_segments).map_or(0, |bounds| {
+            (bounds.min.x.abs_diff(bounds.max.x) as u128 + 1)
+                .saturating_mul(bounds.min.y.abs_diff(bounds.max.y) as u128 + 1)
+        });
+
+        if area <= Self::DENSE_LIMIT {
+            Backend::Dense
+        } else {
+            Backend::Intersections
+        }
+    }
+}
+
+/// The smallest rectangle holding a set of points, corners included.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    /// A box holding just `point`.
+    pub fn new(point: Point) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// The box around `points`, or `None` if there are none.
+    pub fn of<I: IntoIterator<Item = Point>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Self::new(first), |mut bounds, point| {
+            bounds.include(point);
+            bounds
+        }))
+    }
+
+    /// Grows the box to hold `point`.
+    pub fn include(&mut self, point: Point) {
+        self.min = Point::new(cmp::min(self.min.x, point.x), cmp::min(self.min.y, point.y));
+        self.max = Point::new(cmp::max(self.max.x, point.x), cmp::max(self.max.y, point.y));
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize + 1
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    // the (row, col) of `point` in a grid covering the box
+    fn cell(&self, point: Point) -> (usize, usize) {
+        (
+            (point.y - self.min.y) as usize,
+            (point.x - self.min.x) as usize,
+        )
+    }
+}
+
+/// The box around the ends of every segment, or `None` if there are no segments.
+pub fn bounding_box(line_segments: &[LineSegment]) -> Option<BoundingBox> {
+    BoundingBox::of(line_segments.iter().flat_map(|line| [line.start, line.end]))
+}
+
+pub struct Diagram {
+    points: Points,
+    raster: Raster,
+}
+
+enum Points {
+    Dense(BoundingBox, Grid<usize>),
+    Sparse(HashMap<Point, usize>),
+    Segments(Vec<LineSegment>),
+}
+
+impl Diagram {
+    /// A diagram with a count for every point in `bounds`. Every segment
+    /// added must lie inside it.
+    pub fn new(bounds: BoundingBox) -> Self {
+        Self {
+            points: Points::Dense(bounds, Grid::new(bounds.width(), bounds.height(), 0)),
+            raster: Raster::default(),
+        }
+    }
+
+    /// A diagram of any size, growing with the number of points covered.
+    pub fn sparse() -> Self {
+        Self {
+            points: Points::Sparse(HashMap::new()),
+            raster: Raster::default(),
+        }
+    }
+
+    /// A diagram of any size, growing with the number of segments added and
+    /// the points of those not horizontal, vertical or at 45 degrees.
+    pub fn intersections() -> Self {
+        Self {
+            points: Points::Segments(Vec::new()),
+            raster: Raster::default(),
+        }
+    }
+
+    pub fn with_raster(mut self, raster: Raster) -> Self {
+        self.raster = raster;
+        self
+    }
+
+    pub fn add_line_segment(&mut self, line_segment: &LineSegment, allow_diagonals: bool) {
+        if !allow_diagonals && line_segment.is_diagonal() {
+            return;
+        }
+
+        match &mut self.points {
+            Points::Dense(bounds, points) => {
+                for point in &line_segment.rasterise(self.raster) {
+                    points[bounds.cell(*point)] += 1;
+                }
+            }
+            Points::Sparse(points) => {
+                for point in line_segment.rasterise(self.raster) {
+                    *points.entry(point).or_default() += 1;
+                }
+            }
+            Points::Segments(segments) => segments.push(*line_segment),
+        }
+    }
+
+    pub fn count_points_where_lines_overlap(&self) -> usize {
+        match &self.points {
+            Points::Dense(_, points) => points.iter().filter(|&sum| *sum > 1).count(),
+            Points::Sparse(points) => points.values().filter(|&sum| *sum > 1).count(),
+            Points::Segments(segments) => {
+                let (lattice, other): (Vec<LineSegment>, Vec<LineSegment>) =
+                    segments.iter().partition(|segment| {
+                        self.raster == Raster::LatticePoints || segment.is_lattice()
+                    });
+
+                let mut rasterised = HashMap::new();
+                for segment in &other {
+                    for point in segment.rasterise(self.raster) {
+                        *rasterised.entry(point).or_default() += 1;
+                    }
+                }
+
+                count_lattice_overlaps(&lattice, &rasterised)
+            }
+        }
+    }
+
+    /// The points the diagram covers: the box it was made with if it is
+    /// dense, otherwise the box around every point a line covers.
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        match &self.points {
+            Points::Dense(bounds, _) => Some(*bounds),
+            _ => BoundingBox::of(self.sparse_counts().into_keys()),
+        }
+    }
+
+    /// The largest number of points `counts` and the drawings built on it
+    /// will lay out.
+    pub const RENDER_LIMIT: u128 = 1 << 24;
+
+    /// The number of lines covering each point in `bounds()`, with the
+    /// top-left cell at its minimum corner, unless there are more than
+    /// `RENDER_LIMIT` points.
+    pub fn counts(&self) -> std::result::Result<Grid<usize>, DiagramTooLarge> {
+        if let Points::Dense(bounds, points) = &self.points {
+            check_render_size(bounds)?;
+            return Ok(points.clone());
+        }
+
+        let sparse = self.sparse_counts();
+        let bounds = match BoundingBox::of(sparse.keys().copied()) {
+            Some(bounds) => bounds,
+            None => return Ok(Grid::new(0, 0, 0)),
+        };
+        check_render_size(&bounds)?;
+
+        let mut counts = Grid::new(bounds.width(), bounds.height(), 0);
+        for (point, count) in sparse {
+            counts[bounds.cell(point)] = count;
+        }
+
+        Ok(counts)
+    }
+
+    fn sparse_counts(&self) -> HashMap<Point, usize> {
+        match &self.points {
+            Points::Dense(bounds, points) => points
+                .positions()
+                .map(|(row, col)| {
+                    let point = Point::new(bounds.min.x + col as i64, bounds.min.y + row as i64);
+                    (point, points[(row, col)])
+                })
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+            Points::Sparse(points) => points.clone(),
+            Points::Segments(segments) => {
+                let mut points = HashMap::new();
+                for segment in segments {
+                    for point in segment.rasterise(self.raster) {
+                        *points.entry(point).or_default() += 1;
+                    }
+                }
+                points
+            }
+        }
+    }
+
+    /// Writes the diagram as a binary PPM image, coloured by how many lines cover each point.
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let counts = self
+            .counts()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+        write!(writer, "P6\n{} {}\n255\n", counts.width(), counts.height())?;
+        writer.write_all(&heatmap(&counts))
+    }
+
+    /// Writes the diagram as a PNG image, coloured by how many lines cover each point.
+    #[cfg(feature = "png")]
+    pub fn write_png<W: Write>(&self, writer: W) -> std::result::Result<(), png::EncodingError> {
+        let counts = self
+            .counts()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+        let mut encoder = png::Encoder::new(writer, counts.width() as u32, counts.height() as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder.write_header()?.write_image_data(&heatmap(&counts))
+    }
+}
+
+/// The diagram as drawn in the puzzle: `.` where no line is, otherwise the
+/// number of lines, with `#` for more than 9. Formatting fails if the
+/// diagram is too large to draw, which `Diagram::counts` reports.
+impl fmt::Display for Diagram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts = self.counts().map_err(|_| fmt::Error)?;
+
+        let rows: Vec<String> = counts
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|&count| match count {
+                        0 => '.',
+                        1..=9 => char::from_digit(count as u32, 10).unwrap(),
+                        _ => '#',
+                    })
+                    .collect()
+            })
+            .collect();
+
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+/// A diagram covers more points than `Diagram::RENDER_LIMIT`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct DiagramTooLarge {
+    pub width: u128,
+    pub height: u128,
+}
+
+impl fmt::Display for DiagramTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the diagram is {} by {} points, more than the {} that can be drawn",
+            self.width,
+            self.height,
+            Diagram::RENDER_LIMIT
+        )
+    }
+}
+
+impl Error for DiagramTooLarge {}
+
+// each side fits in a u128, but their product only up to 2^128 - 1
+fn check_render_size(bounds: &BoundingBox) -> std::result::Result<(), DiagramTooLarge> {
+    let width = bounds.min.x.abs_diff(bounds.max.x) as u128 + 1;
+    let height = bounds.min.y.abs_diff(bounds.max.y) as u128 + 1;
+
+    if width.saturating_mul(height) > Diagram::RENDER_LIMIT {
+        return Err(DiagramTooLarge { width, height });
+    }
+
+    Ok(())
+}
+
+// RGB pixels running from black through red and yellow to white as the count
+// approaches the highest count in the diagram
+fn heatmap(counts: &Grid<usize>) -> Vec<u8> {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    counts
+        .iter()
+        .flat_map(|&count| {
+            let heat = count * 255 * 3 / max;
+            [
+                heat.min(255) as u8,
+                heat.saturating_sub(255).min(255) as u8,
+                heat.saturating_sub(510).min(255) as u8,
+            ]
+        })
+        .collect()
+}
+
+pub fn count_overlapping_points(line_segments: &[LineSegment], allow_diagonals: bool) -> usize {
+    let backend = Backend::choose(line_segments);
+    count_overlapping_points_with(line_segments, allow_diagonals, backend)
+}
+
+pub fn count_overlapping_points_with(
+    line_segments: &[LineSegment],
+    allow_diagonals: bool,
+    backend: Backend,
+) -> usize {
+    build_diagram(line_segments, allow_diagonals, backend).count_points_where_lines_overlap()
+}
+
+pub fn build_diagram(
+    line_segments: &[LineSegment],
+    allow_diagonals: bool,
+    backend: Backend,
+) -> Diagram {
+    let mut diagram = match backend {
+        Backend::Dense => {
+            Diagram::new(bounding_box(line_segments).unwrap_or(BoundingBox::new(Point::new(0, 0))))
+        }
+        Backend::Sparse => Diagram::sparse(),
+        Backend::Intersections => Diagram::intersections(),
+    };
+
+    for line_segment in line_segments {
+        diagram.add_line_segment(line_segment, allow_diagonals);
+    }
+
+    diagram
+}
+
+// a line by its direction and offset
+type Line = ((i128, i128), i128);
+
+// A segment as the lattice points `p` with `p . direction` in `from..=to`,
+// stepping by `step`, on the line `direction.y * x - direction.x * y == offset`.
+// Everything is in i128 so that segments spanning the whole i64 range fit.
+struct LatticeSegment {
+    direction: (i128, i128),
+    offset: i128,
+    from: i128,
+    to: i128,
+}
+
+impl LatticeSegment {
+    fn new(segment: &LineSegment) -> Self {
+        let (x0, y0) = (segment.start.x as i128, segment.start.y as i128);
+        let (x1, y1) = (segment.end.x as i128, segment.end.y as i128);
+
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        // both differences are at most u64::MAX
+        let steps = gcd(dx.unsigned_abs() as u64, dy.unsigned_abs() as u64).max(1) as i128;
+        // point every line the same way so that parallel segments share a direction
+        let direction = match (dx / steps, dy / steps) {
+            (0, 0) => (1, 0),
+            (a, b) if a < 0 || (a == 0 && b < 0) => (-a, -b),
+            direction => direction,
+        };
+
+        let along = |x: i128, y: i128| x * direction.0 + y * direction.1;
+        let (from, to) = (along(x0, y0), along(x1, y1));
+
+        Self {
+            direction,
+            offset: direction.1 * x0 - direction.0 * y0,
+            from: from.min(to),
+            to: from.max(to),
+        }
+    }
+
+    fn line(&self) -> Line {
+        (self.direction, self.offset)
+    }
+
+    fn step(&self) -> i128 {
+        self.direction.0 * self.direction.0 + self.direction.1 * self.direction.1
+    }
+
+    fn position(&self, (x, y): (i128, i128)) -> i128 {
+        x * self.direction.0 + y * self.direction.1
+    }
+
+    // The lattice point where two segments on different lines cross, if any.
+    // Horizontal, vertical and 45 degree lines keep every product far inside
+    // i128; other slopes only overflow with coordinates beyond about 2^40.
+    fn crossing(&self, other: &Self) -> Option<(i128, i128)> {
+        let ((a1, b1), c1) = self.line();
+        let ((a2, b2), c2) = other.line();
+
+        let det = a1 * b2 - a2 * b1;
+        if det == 0 {
+            return None;
+        }
+
+        let (x, y) = (a1 * c2 - a2 * c1, b1 * c2 - b2 * c1);
+        if x % det != 0 || y % det != 0 {
+            return None;
+        }
+
+        let point = (x / det, y / det);
+        let on_both = [self, other]
+            .iter()
+            .all(|segment| (segment.from..=segment.to).contains(&segment.position(point)));
+
+        on_both.then_some(point)
+    }
+}
+
+// Counts the lattice points covered by at least two segments, or by the
+// segments and the `rasterised` points together, without visiting every point
+// of the segments. Segments on the same line overlap in intervals, found with a
+// sweep along the line; segments on different lines cross at one point at most,
+// found by trying every pair.
+fn count_lattice_overlaps(segments: &[LineSegment], rasterised: &HashMap<Point, usize>) -> usize {
+    let segments: Vec<LatticeSegment> = segments.iter().map(LatticeSegment::new).collect();
+
+    let mut lines: HashMap<Line, Vec<&LatticeSegment>> = HashMap::new();
+    for segment in &segments {
+        lines.entry(segment.line()).or_default().push(segment);
+    }
+
+    // the intervals along each line covered at least twice
+    let mut overlaps: HashMap<Line, Vec<(i128, i128)>> = HashMap::new();
+    let mut count = 0;
+
+    for (line, on_line) in &lines {
+        let step = on_line[0].step();
+        let mut events: Vec<(i128, i32)> = on_line
+            .iter()
+            .flat_map(|segment| [(segment.from, 1), (segment.to + step, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let mut covering = 0;
+        let mut start = 0;
+        for (position, change) in events {
+            let was_overlapping = covering > 1;
+            covering += change;
+
+            if !was_overlapping && covering > 1 {
+                start = position;
+            } else if was_overlapping && covering <= 1 && position > start {
+                count += ((position - start) / step) as usize;
+                overlaps
+                    .entry(*line)
+                    .or_default()
+                    .push((start, position - step));
+            }
+        }
+    }
+
+    // lines through each crossing point
+    let mut crossings: HashMap<(i128, i128), HashSet<Line>> = HashMap::new();
+    for (i, first) in segments.iter().enumerate() {
+        for second in &segments[i + 1..] {
+            if let Some(point) = first.crossing(second) {
+                let lines = crossings.entry(point).or_default();
+                lines.insert(first.line());
+                lines.insert(second.line());
+            }
+        }
+    }
+
+    // a crossing is new unless an overlap already counted it, and counted
+    // too often if it's on the overlaps of several lines
+    for (point, through) in crossings {
+        let counted = through
+            .iter()
+            .filter(|line| {
+                let position = lines[*line][0].position(point);
+                overlaps.get(*line).is_some_and(|intervals| {
+                    intervals
+                        .iter()
+                        .any(|(from, to)| (*from..=*to).contains(&position))
+                })
+            })
+            .count();
+
+        match counted {
+            0 => count += 1,
+            n => count -= n - 1,
+        }
+    }
+
+    // a rasterised point is new unless the segments already cover it twice,
+    // found by looking it up on the line through it in each direction
+    let directions: HashSet<(i128, i128)> = lines.keys().map(|(direction, _)| *direction).collect();
+    for (point, times) in rasterised {
+        let point = (point.x as i128, point.y as i128);
+        let covering = directions
+            .iter()
+            .filter_map(|&(a, b)| lines.get(&((a, b), b * point.0 - a * point.1)))
+            .flatten()
+            .filter(|segment| (segment.from..=segment.to).contains(&segment.position(point)))
+            .count();
+
+        if covering < 2 && covering + times > 1 {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+pub fn read_line_segments(input: &str) -> Result<Vec<LineSegment>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| read_line_segment(line).map_err(|err| err.offset_lines(i)))
+        .collect()
+}
+
+pub fn read_line_segment(line: &str) -> Result<LineSegment> {
+    // parse '0,9 -> 5,9'
+    let mut points = line.split(" -> ");
+
+    let start = next_token(line, points.next(), "a point")?;
+    let end = next_token(line, points.next(), "' -> '")?;
+
+    let start = read_point(line, start)?;
+    let end = read_point(line, end)?;
+
+    Ok(LineSegment::new(start, end))
+}
+
+fn read_point(line: &str, point: &str) -> Result<Point> {
+    // parse '0,9'
+    let mut parts = point.split(',');
+
+    let (x, y) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(x), Some(y), None) => (x, y),
+        _ => return Err(ParseError::invalid_token(line, point, "a point like '0,9'")),
+    };
+
+    let x = parse_token(line, x, "an x coordinate")?;
+    let y = parse_token(line, y, "a y coordinate")?;
+
+    Ok(Point::new(x, y))
+}
+
+#[cfg(test)]
+mod day5_tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_read_line_segment() {
+        let input = "0,9 -> 5,9";
+        let line = read_line_segment(input).unwrap();
+
+        assert_eq!(line.start, Point::new(0, 9));
+        assert_eq!(line.end, Point::new(5, 9));
+    }
+
+    #[test]
+    fn test_read_line_segment_invalid() {
+        let err = read_line_segment("0,9 -> 5").unwrap_err();
+        assert_eq!(
+            "1:8: expected a point like '0,9', found `5`",
+            err.to_string()
+        );
+
+        let err = read_line_segment("0,9 => 5,9").unwrap_err();
+        assert_eq!("1:11: expected ' -> ', found end of line", err.to_string());
+
+        let err = read_line_segment("0,x9 -> 5,9").unwrap_err();
+        assert_eq!("1:3: expected a y coordinate, found `x9`", err.to_string());
+    }
+
+    #[test]
+    fn test_read_negative_coordinates() {
+        let line = read_line_segment("-3,4 -> 2,-1").unwrap();
+
+        assert_eq!(line.start, Point::new(-3, 4));
+        assert_eq!(line.end, Point::new(2, -1));
+        assert!(line.is_45_degree());
+    }
+
+    #[test]
+    fn test_read_sample_input() {
+        let input = fs::read_to_string("./data/sample5.txt").unwrap();
+
+        let line_segments = read_line_segments(&input).unwrap();
+        assert_eq!(10, line_segments.len());
+
+        let line_segment = line_segments[3];
+        assert_eq!(line_segment.start, Point::new(2, 2));
+        assert_eq!(line_segment.end, Point::new(2, 1));
+
+        let line_segment = line_segments[7];
+        assert_eq!(line_segment.start, Point::new(3, 4));
+        assert_eq!(line_segment.end, Point::new(1, 4));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let input = fs::read_to_string("./data/sample5.txt").unwrap();
+        let line_segments = read_line_segments(&input).unwrap();
+
+        let bounds = bounding_box(&line_segments).unwrap();
+        assert_eq!(bounds.min, Point::new(0, 0));
+        assert_eq!(bounds.max, Point::new(9, 9));
+        assert_eq!((bounds.width(), bounds.height()), (10, 10));
+
+        let line_segments = read_line_segments("-3,4 -> 2,4\n5,-2 -> 5,1").unwrap();
+        let bounds = bounding_box(&line_segments).unwrap();
+        assert_eq!(bounds.min, Point::new(-3, -2));
+        assert_eq!(bounds.max, Point::new(5, 4));
+        assert_eq!((bounds.width(), bounds.height()), (9, 7));
+        assert!(bounds.contains(Point::new(0, 0)));
+        assert!(!bounds.contains(Point::new(-4, 0)));
+
+        assert_eq!(bounding_box(&[]), None);
+    }
+
+    mod test_diagram {
+        use super::*;
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        #[test]
+        fn test_count_points_where_lines_overlap() {
+            let input = fs::read_to_string("./data/sample5.txt").unwrap();
+            let line_segments = read_line_segments(&input).unwrap();
+
+            let mut diagram = Diagram::new(bounding_box(&line_segments).unwrap());
+
+            for line_segment in &line_segments {
+                diagram.add_line_segment(line_segment, false);
+            }
+
+            assert_eq!(5, diagram.count_points_where_lines_overlap());
+        }
+
+        #[test]
+        fn test_count_points_where_lines_overlap_including_diagonal() {
+            let input = fs::read_to_string("./data/sample5.txt").unwrap();
+            let line_segments = read_line_segments(&input).unwrap();
+
+            let mut diagram = Diagram::new(bounding_box(&line_segments).unwrap());
+
+            for line_segment in &line_segments {
+                diagram.add_line_segment(line_segment, true);
+            }
+
+            assert_eq!(12, diagram.count_points_where_lines_overlap());
+        }
+
+        fn random_segments(
+            seed: u64,
+            count: usize,
+            size: i64,
+            any_slope: bool,
+        ) -> Vec<LineSegment> {
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            (0..count)
+                .map(|_| {
+                    let start = Point::new(rng.gen_range(0..size), rng.gen_range(0..size));
+                    let end = if any_slope {
+                        Point::new(rng.gen_range(0..size), rng.gen_range(0..size))
+                    } else {
+                        let length = rng.gen_range(0..size);
+                        let (dx, dy) = [(1, 0), (0, 1), (1, 1), (1, -1)][rng.gen_range(0..4)];
+                        Point::new(start.x + dx * length, start.y + dy * length)
+                    };
+                    LineSegment::new(start, end)
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_backends_agree_on_sample() {
+            let input = fs::read_to_string("./data/sample5.txt").unwrap();
+            let line_segments = read_line_segments(&input).unwrap();
+
+            for backend in [Backend::Dense, Backend::Sparse, Backend::Intersections] {
+                assert_eq!(
+                    5,
+                    count_overlapping_points_with(&line_segments, false, backend)
+                );
+                assert_eq!(
+                    12,
+                    count_overlapping_points_with(&line_segments, true, backend)
+                );
+            }
+        }
+
+        #[test]
+        fn test_backends_agree_on_random_segments() {
+            for seed in 0..20 {
+                let line_segments = random_segments(seed, 60, 40, false);
+
+                for allow_diagonals in [false, true] {
+                    let dense = count_overlapping_points_with(
+                        &line_segments,
+                        allow_diagonals,
+                        Backend::Dense,
+                    );
+                    let sparse = count_overlapping_points_with(
+                        &line_segments,
+                        allow_diagonals,
+                        Backend::Sparse,
+                    );
+                    let intersections = count_overlapping_points_with(
+                        &line_segments,
+                        allow_diagonals,
+                        Backend::Intersections,
+                    );
+
+                    assert_eq!(dense, sparse, "seed {}", seed);
+                    assert_eq!(dense, intersections, "seed {}", seed);
+                }
+            }
+        }
+
+        #[test]
+        fn test_intersections_any_slope() {
+            for seed in 0..20 {
+                let line_segments = random_segments(seed, 40, 30, true);
+
+                let bounds = bounding_box(&line_segments).unwrap();
+                let mut dense = Diagram::new(bounds).with_raster(Raster::LatticePoints);
+                let mut sparse = Diagram::sparse().with_raster(Raster::LatticePoints);
+                let mut intersections = Diagram::intersections().with_raster(Raster::LatticePoints);
+                for line_segment in &line_segments {
+                    dense.add_line_segment(line_segment, true);
+                    sparse.add_line_segment(line_segment, true);
+                    intersections.add_line_segment(line_segment, true);
+                }
+
+                let expected = dense.count_points_where_lines_overlap();
+                assert_eq!(
+                    expected,
+                    sparse.count_points_where_lines_overlap(),
+                    "seed {}",
+                    seed
+                );
+                assert_eq!(
+                    expected,
+                    intersections.count_points_where_lines_overlap(),
+                    "seed {}",
+                    seed
+                );
+            }
+        }
+
+        #[test]
+        fn test_intersections_rasterise_other_slopes() {
+            for seed in 0..20 {
+                let line_segments = random_segments(seed, 40, 30, true);
+
+                let dense = count_overlapping_points_with(&line_segments, true, Backend::Dense);
+                let intersections =
+                    count_overlapping_points_with(&line_segments, true, Backend::Intersections);
+
+                assert_eq!(dense, intersections, "seed {}", seed);
+            }
+        }
+
+        #[test]
+        fn test_display() {
+            let input = fs::read_to_string("./data/sample5.txt").unwrap();
+            let line_segments = read_line_segments(&input).unwrap();
+
+            let expected = "\
+.......1..
+..1....1..
+..1....1..
+.......1..
+.112111211
+..........
+..........
+..........
+..........
+222111....";
+            for backend in [Backend::Dense, Backend::Sparse, Backend::Intersections] {
+                let diagram = build_diagram(&line_segments, false, backend);
+                assert_eq!(expected, diagram.to_string());
+            }
+
+            let expected = "\
+1.1....11.
+.111...2..
+..2.1.111.
+...1.2.2..
+.112313211
+...1.2....
+..1...1...
+.1.....1..
+1.......1.
+222111....";
+            for backend in [Backend::Dense, Backend::Sparse, Backend::Intersections] {
+                let diagram = build_diagram(&line_segments, true, backend);
+                assert_eq!(expected, diagram.to_string());
+            }
+        }
+
+        #[test]
+        fn test_negative_coordinates() {
+            let line_segments =
+                read_line_segments("-2,-2 -> 2,2\n-2,0 -> 2,0\n0,-3 -> 0,1\n-2,2 -> 2,-2").unwrap();
+
+            for backend in [Backend::Dense, Backend::Sparse, Backend::Intersections] {
+                let diagram = build_diagram(&line_segments, true, backend);
+
+                assert_eq!(1, diagram.count_points_where_lines_overlap());
+                assert_eq!(Point::new(-2, -3), diagram.bounds().unwrap().min);
+                assert_eq!(
+                    "\
+..1..
+1.1.1
+.111.
+11411
+.111.
+1...1",
+                    diagram.to_string()
+                );
+            }
+        }
+
+        #[test]
+        fn test_write_ppm() {
+            let line_segments = vec![
+                LineSegment::new(Point::new(0, 0), Point::new(2, 0)),
+                LineSegment::new(Point::new(1, 0), Point::new(1, 1)),
+            ];
+            let diagram = build_diagram(&line_segments, false, Backend::Sparse);
+
+            let mut ppm = Vec::new();
+            diagram.write_ppm(&mut ppm).unwrap();
+
+            let header = b"P6\n3 2\n255\n";
+            assert_eq!(&ppm[..header.len()], header);
+
+            let pixels = &ppm[header.len()..];
+            assert_eq!(pixels.len(), 3 * 2 * 3);
+            // one line is half as hot as two
+            assert_eq!(&pixels[0..3], &[255, 127, 0]);
+            assert_eq!(&pixels[3..6], &[255, 255, 255]);
+            assert_eq!(&pixels[9..12], &[0, 0, 0]);
+        }
+
+        #[test]
+        fn test_too_large_to_draw() {
+            let line_segments = vec![LineSegment::new(Point::new(0, 0), Point::new(4096, 4096))];
+            let diagram = build_diagram(&line_segments, true, Backend::Sparse);
+
+            assert_eq!(
+                diagram.counts().unwrap_err(),
+                DiagramTooLarge {
+                    width: 4097,
+                    height: 4097
+                }
+            );
+            assert!(diagram.write_ppm(Vec::new()).is_err());
+
+            let (min, max) = (i64::MIN, i64::MAX);
+            let line_segments = vec![
+                LineSegment::new(Point::new(min, min), Point::new(min, min)),
+                LineSegment::new(Point::new(max, max), Point::new(max, max)),
+            ];
+            let diagram = build_diagram(&line_segments, true, Backend::Sparse);
+
+            assert_eq!(
+                diagram.counts().unwrap_err().to_string(),
+                format!(
+                    "the diagram is {0} by {0} points, more than the 16777216 that can be drawn",
+                    1u128 << 64
+                )
+            );
+        }
+
+        #[test]
+        fn test_huge_coordinates() {
+            let line_segments = vec![
+                LineSegment::new(Point::new(0, 1_000_000), Point::new(1_000_000, 1_000_000)),
+                LineSegment::new(
+                    Point::new(999_990, 999_990),
+                    Point::new(1_000_000, 1_000_000),
+                ),
+                LineSegment::new(
+                    Point::new(500_000, 1_000_000),
+                    Point::new(500_009, 1_000_000),
+                ),
+            ];
+
+            assert_eq!(
+                11,
+                count_overlapping_points_with(&line_segments, true, Backend::Sparse)
+            );
+            assert_eq!(
+                11,
+                count_overlapping_points_with(&line_segments, true, Backend::Intersections)
+            );
+        }
+    }
+
+    #[test]
+    fn test_choose_backend() {
+        let input = fs::read_to_string("./data/sample5.txt").unwrap();
+        let line_segments = read_line_segments(&input).unwrap();
+        assert_eq!(Backend::Dense, Backend::choose(&line_segments));
+        assert_eq!(Backend::Dense, Backend::choose(&[]));
+
+        let line_segments =
+            read_line_segments("0,0 -> 100000,0\n0,0 -> 100000,100000\n0,0 -> 2,1").unwrap();
+        assert_eq!(Backend::Intersections, Backend::choose(&line_segments));
+        // the odd slope is rasterised, so (1, 1) is on it and the diagonal
+        assert_eq!(2, count_overlapping_points(&line_segments, true));
+        assert_eq!(
+            2,
+            count_overlapping_points_with(&line_segments, true, Backend::Sparse)
+        );
+    }
+
+    #[test]
+    fn test_extreme_coordinates() {
+        let (min, max) = (i64::MIN, i64::MAX);
+        let line_segments = vec![
+            LineSegment::new(Point::new(min, 0), Point::new(max, 0)),
+            LineSegment::new(Point::new(0, min), Point::new(0, max)),
+            LineSegment::new(Point::new(min, min), Point::new(max, max)),
+            LineSegment::new(Point::new(max, min + 1), Point::new(min + 1, max)),
+            LineSegment::new(Point::new(max - 9, 0), Point::new(max, 0)),
+        ];
+
+        assert_eq!(11, count_overlapping_points(&line_segments, false));
+        assert_eq!(11, count_overlapping_points(&line_segments, true));
+
+        let line = LineSegment::new(Point::new(min, 0), Point::new(max, 1));
+        assert_eq!(
+            line.rasterise(Raster::LatticePoints),
+            vec![line.start, line.end]
+        );
+        let line = LineSegment::new(Point::new(max, max), Point::new(max - 3, max - 1));
+        assert_eq!(
+            line.points(),
+            vec![
+                Point::new(max, max),
+                Point::new(max - 1, max),
+                Point::new(max - 2, max - 1),
+                Point::new(max - 3, max - 1),
+            ]
+        );
+    }
+
+    mod test_line_segment {
+        use super::*;
+
+        #[test]
+        fn test_points_horizontal() {
+            let line = LineSegment::new(Point::new(0, 9), Point::new(2, 9));
+            let points = line.points();
+
+            assert_eq!(3, points.len());
+            assert_eq!(Point::new(0, 9), points[0]);
+            assert_eq!(Point::new(1, 9), points[1]);
+            assert_eq!(Point::new(2, 9), points[2]);
+        }
+
+        #[test]
+        fn test_points_vertical() {
+            let line = LineSegment::new(Point::new(3, 0), Point::new(3, 3));
+            let points = line.points();
+
+            assert_eq!(4, points.len());
+            assert_eq!(Point::new(3, 0), points[0]);
+            assert_eq!(Point::new(3, 1), points[1]);
+            assert_eq!(Point::new(3, 2), points[2]);
+            assert_eq!(Point::new(3, 3), points[3]);
+        }
+
+        #[test]
+        fn test_points_diagonal() {
+            let line = LineSegment::new(Point::new(0, 0), Point::new(3, 3));
+            let points = line.points();
+
+            assert_eq!(4, points.len());
+            assert_eq!(Point::new(0, 0), points[0]);
+            assert_eq!(Point::new(1, 1), points[1]);
+            assert_eq!(Point::new(2, 2), points[2]);
+            assert_eq!(Point::new(3, 3), points[3]);
+
+            let line = LineSegment::new(Point::new(3, 3), Point::new(0, 0));
+            let points = line.points();
+
+            assert_eq!(4, points.len());
+            assert_eq!(Point::new(3, 3), points[0]);
+            assert_eq!(Point::new(2, 2), points[1]);
+            assert_eq!(Point::new(1, 1), points[2]);
+            assert_eq!(Point::new(0, 0), points[3]);
+
+            let line = LineSegment::new(Point::new(0, 3), Point::new(3, 0));
+            let points = line.points();
+
+            assert_eq!(4, points.len());
+            assert_eq!(Point::new(0, 3), points[0]);
+            assert_eq!(Point::new(1, 2), points[1]);
+            assert_eq!(Point::new(2, 1), points[2]);
+            assert_eq!(Point::new(3, 0), points[3]);
+
+            let line = LineSegment::new(Point::new(3, 0), Point::new(0, 3));
+            let points = line.points();
+
+            assert_eq!(4, points.len());
+            assert_eq!(Point::new(3, 0), points[0]);
+            assert_eq!(Point::new(2, 1), points[1]);
+            assert_eq!(Point::new(1, 2), points[2]);
+            assert_eq!(Point::new(0, 3), points[3]);
+        }
+
+        #[test]
+        fn test_is_45_degree() {
+            let line = LineSegment::new(Point::new(5, 5), Point::new(8, 2));
+            assert!(line.is_diagonal());
+            assert!(line.is_45_degree());
+
+            let line = LineSegment::new(Point::new(0, 0), Point::new(4, 2));
+            assert!(line.is_diagonal());
+            assert!(!line.is_45_degree());
+
+            let line = LineSegment::new(Point::new(0, 0), Point::new(0, 2));
+            assert!(!line.is_diagonal());
+            assert!(!line.is_45_degree());
+        }
+
+        #[test]
+        fn test_points_shallow() {
+            let line = LineSegment::new(Point::new(0, 1), Point::new(5, 3));
+
+            assert_eq!(
+                line.points(),
+                vec![
+                    Point::new(0, 1),
+                    Point::new(1, 1),
+                    Point::new(2, 2),
+                    Point::new(3, 2),
+                    Point::new(4, 3),
+                    Point::new(5, 3),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_points_steep_and_reversed() {
+            let line = LineSegment::new(Point::new(2, 3), Point::new(0, 0));
+
+            assert_eq!(
+                line.points(),
+                vec![
+                    Point::new(2, 3),
+                    Point::new(1, 2),
+                    Point::new(1, 1),
+                    Point::new(0, 0),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_points_cover_every_column() {
+            let line = LineSegment::new(Point::new(1, 7), Point::new(11, 3));
+            let points = line.points();
+
+            assert_eq!(points.len(), 11);
+            for (x, point) in (1..=11).zip(&points) {
+                assert_eq!(point.x, x);
+            }
+            assert_eq!(points.last(), Some(&line.end));
+        }
+
+        #[test]
+        fn test_lattice_points() {
+            let line = LineSegment::new(Point::new(0, 0), Point::new(6, 4));
+
+            assert_eq!(
+                line.rasterise(Raster::LatticePoints),
+                vec![Point::new(0, 0), Point::new(3, 2), Point::new(6, 4)]
+            );
+
+            let line = LineSegment::new(Point::new(5, 0), Point::new(0, 3));
+            assert_eq!(
+                line.rasterise(Raster::LatticePoints),
+                vec![Point::new(5, 0), Point::new(0, 3)]
+            );
+        }
+
+        #[test]
+        fn test_rasters_agree_at_45_degrees() {
+            let line = LineSegment::new(Point::new(7, 1), Point::new(2, 6));
+
+            assert_eq!(line.rasterise(Raster::LatticePoints), line.points());
+            assert_eq!(line.points().len(), 6);
+        }
+    }
+}
@@ -0,0 +1,968 @@
+use std::{
+    cmp, error, fmt,
+    io::{self, Write},
+    iter,
+    ops::{Add, Mul},
+};
+
+use crate::{error::parse_token, Day, ParseError, Result};
+
+pub struct Day6;
+
+impl Day for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<u8>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_fish_ages(input.trim())
+    }
+
+    fn part1(fish_ages: &Self::Input) -> Self::Output {
+        let mut fish = Fish::new(fish_ages);
+        fish.simulate_days(80);
+        fish.count()
+    }
+
+    fn part2(fish_ages: &Self::Input) -> Self::Output {
+        let mut fish = Fish::new(fish_ages);
+        fish.simulate_days(256);
+        fish.count()
+    }
+}
+
+/// A number of fish, which can grow past any fixed-size integer given enough days.
+pub trait FishCount:
+    Copy + PartialEq + fmt::Debug + fmt::Display + Add<Output = Self> + Mul<Output = Self>
+{
+    fn from_u64(n: u64) -> Self;
+
+    /// `self + other`, or `None` if the sum doesn't fit.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// `self * other`, or `None` if the product doesn't fit.
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+impl FishCount for u64 {
+    fn from_u64(n: u64) -> Self {
+        n
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u64::checked_add(self, other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        u64::checked_mul(self, other)
+    }
+}
+
+impl FishCount for u128 {
+    fn from_u64(n: u64) -> Self {
+        n as u128
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u128::checked_add(self, other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        u128::checked_mul(self, other)
+    }
+}
+
+/// A count modulo the prime `P`, for when the exact count is too big to keep.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Modular<const P: u64>(u64);
+
+impl<const P: u64> Modular<P> {
+    pub fn value(self) -> u64 {
+        self.0
+    }
+}
+
+impl<const P: u64> FishCount for Modular<P> {
+    fn from_u64(n: u64) -> Self {
+        Self(n % P)
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+/// A number of fish too large to count with the chosen `FishCount`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct CountOverflow;
+
+impl fmt::Display for CountOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the number of fish is too large to count")
+    }
+}
+
+impl error::Error for CountOverflow {}
+
+impl<const P: u64> Add for Modular<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(((self.0 as u128 + other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Mul for Modular<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self((self.0 as u128 * other.0 as u128 % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> fmt::Display for Modular<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// How a population of fish changes from one day to the next.
+///
+/// Each day a fish's timer counts down by one. A fish whose timer is at 0
+/// instead resets it to `reset_age` and spawns `litter_size` new fish with
+/// timers at `newborn_age`. With a `max_lifespan`, fish die once they have
+/// lived that many days, counting fish already alive as born on day 0.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SpawnRules {
+    pub reset_age: u8,
+    pub newborn_age: u8,
+    pub litter_size: u64,
+    pub max_lifespan: Option<usize>,
+}
+
+impl SpawnRules {
+    /// The lanternfish in the puzzle.
+    pub const LANTERNFISH: Self = Self {
+        reset_age: 6,
+        newborn_age: 8,
+        litter_size: 1,
+        max_lifespan: None,
+    };
+
+    /// Rules with the given fields, or an error if fish could never live
+    /// through a day, i.e. `max_lifespan` is `Some(0)`.
+    pub fn new(
+        reset_age: u8,
+        newborn_age: u8,
+        litter_size: u64,
+        max_lifespan: Option<usize>,
+    ) -> std::result::Result<Self, SpawnError> {
+        let rules = Self {
+            reset_age,
+            newborn_age,
+            litter_size,
+            max_lifespan,
+        };
+        rules.check()?;
+
+        Ok(rules)
+    }
+
+    fn check(&self) -> std::result::Result<(), SpawnError> {
+        match self.max_lifespan {
+            Some(0) => Err(SpawnError::NoLifespan),
+            _ => Ok(()),
+        }
+    }
+
+    // the number of timer values a fish can have
+    fn timers(&self) -> usize {
+        cmp::max(self.reset_age, self.newborn_age) as usize + 1
+    }
+
+    // the number of different days lived that are worth telling apart
+    fn lifetimes(&self) -> usize {
+        self.max_lifespan.unwrap_or(1)
+    }
+
+    fn state(&self, lived: usize, timer: usize) -> usize {
+        lived * self.timers() + timer
+    }
+
+    /// How much the population grows each day in the long run: the dominant
+    /// eigenvalue of the day-to-day transition. It is 0 if the fish die out.
+    pub fn growth_rate(&self) -> f64 {
+        const DAYS: usize = 4096;
+
+        // Follow a population through enough days for the dominant eigenvalue
+        // to take over, rescaling as we go, and average the growth over the
+        // second half so that populations which cycle still settle.
+        let size = self.timers() * self.lifetimes();
+        let mut counts = vec![1.0; size];
+        let mut log_growth = 0.0;
+
+        for day in 0..DAYS {
+            let mut next = vec![0.0; size];
+            for (state, &count) in counts.iter().enumerate() {
+                for (successor, multiplier) in self.successors(state) {
+                    next[successor] += count * multiplier as f64;
+                }
+            }
+
+            let total: f64 = next.iter().sum();
+            if total == 0.0 {
+                return 0.0;
+            }
+            if day >= DAYS / 2 {
+                log_growth += total.ln();
+            }
+            counts = next.iter().map(|count| count / total).collect();
+        }
+
+        (log_growth / (DAYS / 2) as f64).exp()
+    }
+
+    // where the fish in a state are a day later, and how many fish each one becomes there
+    fn successors(&self, state: usize) -> Vec<(usize, u64)> {
+        let (lived, timer) = (state / self.timers(), state % self.timers());
+        let mut successors = Vec::with_capacity(2);
+
+        let older = match self.max_lifespan {
+            Some(lifespan) if lived + 1 >= lifespan => None,
+            Some(_) => Some(lived + 1),
+            None => Some(0),
+        };
+
+        if timer == 0 {
+            if let Some(older) = older {
+                successors.push((self.state(older, self.reset_age as usize), 1));
+            }
+            successors.push((self.state(0, self.newborn_age as usize), self.litter_size));
+        } else if let Some(older) = older {
+            successors.push((self.state(older, timer - 1), 1));
+        }
+
+        successors
+    }
+}
+
+impl Default for SpawnRules {
+    fn default() -> Self {
+        Self::LANTERNFISH
+    }
+}
+
+/// Why a population can't be started with some `SpawnRules`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SpawnError {
+    /// `max_lifespan` is `Some(0)`, so there is no day a fish could be alive on.
+    NoLifespan,
+    /// A fish's timer is above both `reset_age` and `newborn_age`.
+    AgeOutOfRange(u8),
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpawnError::NoLifespan => write!(f, "a maximum lifespan of 0 days leaves no fish"),
+            SpawnError::AgeOutOfRange(age) => {
+                write!(f, "no fish can have a timer of {} under these rules", age)
+            }
+        }
+    }
+}
+
+impl error::Error for SpawnError {}
+
+pub struct Fish<C = u64> {
+    rules: SpawnRules,
+    // number of fish in each state, one per timer value for every day lived
+    // i.e. counts[2] = 42 => 42 fish have a timer of 2
+    counts: Vec<C>,
+}
+
+impl Fish {
+    /// Lanternfish with the given timers. Panics if a timer is above 8,
+    /// which `parse_fish_ages` rules out.
+    pub fn new(ages: &[u8]) -> Self {
+        Self::from_ages(ages)
+    }
+}
+
+impl<C: FishCount> Fish<C> {
+    /// Like `new`, counting with `C`: `Fish::<u128>::from_ages(&ages)`.
+    pub fn from_ages(ages: &[u8]) -> Self {
+        Self::with_rules(ages, SpawnRules::default()).expect("a lanternfish timer above 8")
+    }
+
+    /// Fish with the given timers following `rules`, or an error if the
+    /// rules leave no lifespan or a timer is out of their range.
+    pub fn with_rules(ages: &[u8], rules: SpawnRules) -> std::result::Result<Self, SpawnError> {
+        rules.check()?;
+
+        let mut counts = vec![C::from_u64(0); rules.timers() * rules.lifetimes()];
+        for &age in ages {
+            if age as usize >= rules.timers() {
+                return Err(SpawnError::AgeOutOfRange(age));
+            }
+            let state = rules.state(0, age as usize);
+            counts[state] = counts[state] + C::from_u64(1);
+        }
+
+        Ok(Self { rules, counts })
+    }
+
+    pub fn rules(&self) -> SpawnRules {
+        self.rules
+    }
+
+    pub fn count(&self) -> C {
+        self.counts
+            .iter()
+            .fold(C::from_u64(0), |total, &count| total + count)
+    }
+
+    /// The number of fish with each timer value.
+    pub fn ages(&self) -> Vec<C> {
+        let mut ages = vec![C::from_u64(0); self.rules.timers()];
+        for (state, &count) in self.counts.iter().enumerate() {
+            let timer = state % self.rules.timers();
+            ages[timer] = ages[timer] + count;
+        }
+
+        ages
+    }
+
+    /// Simulates one day at a time, yielding `ages()` after each day. It ends
+    /// before the first day on which the fish could no longer be counted.
+    pub fn history(&mut self) -> impl Iterator<Item = Vec<C>> + '_ {
+        iter::from_fn(move || {
+            self.checked_simulate_day().ok()?;
+            Some(self.ages())
+        })
+    }
+
+    pub fn growth_rate(&self) -> f64 {
+        self.rules.growth_rate()
+    }
+
+    pub fn simulate_day(&mut self) {
+        let mut next = vec![C::from_u64(0); self.counts.len()];
+
+        for (state, &count) in self.counts.iter().enumerate() {
+            for (successor, multiplier) in self.rules.successors(state) {
+                next[successor] = next[successor] + count * C::from_u64(multiplier);
+            }
+        }
+
+        self.counts = next;
+    }
+
+    /// Like `simulate_day`, but leaves the fish as they were and returns an
+    /// error if any count would overflow.
+    pub fn checked_simulate_day(&mut self) -> std::result::Result<(), CountOverflow> {
+        let mut next = vec![C::from_u64(0); self.counts.len()];
+
+        for (state, &count) in self.counts.iter().enumerate() {
+            for (successor, multiplier) in self.rules.successors(state) {
+                next[successor] = count
+                    .checked_mul(C::from_u64(multiplier))
+                    .and_then(|born| next[successor].checked_add(born))
+                    .ok_or(CountOverflow)?;
+            }
+        }
+        // ages() and count() add up the states too
+        let total = next
+            .iter()
+            .try_fold(C::from_u64(0), |total, &count| total.checked_add(count));
+        if total.is_none() {
+            return Err(CountOverflow);
+        }
+
+        self.counts = next;
+        Ok(())
+    }
+
+    pub fn simulate_days(&mut self, num_days: usize) {
+        for _ in 0..num_days {
+            self.simulate_day();
+        }
+    }
+
+    /// Moves `num_days` ahead in O(log num_days) steps, by raising the
+    /// matrix for a single day to that power. The matrix has a row for every
+    /// timer value on every day of a lifespan, so this suits short lifespans.
+    /// Counts that don't fit in `C` overflow; `checked_jump_days` reports them.
+    pub fn jump_days(&mut self, num_days: u64) {
+        let transition = Transition::<C>::day(&self.rules).pow(num_days);
+        let counts = self.counts.clone();
+
+        for (state, count) in self.counts.iter_mut().enumerate() {
+            *count = counts
+                .iter()
+                .enumerate()
+                .fold(C::from_u64(0), |total, (from, &fish)| {
+                    total + transition.0[state][from] * fish
+                });
+        }
+    }
+
+    /// Like `jump_days`, but leaves the fish as they were and returns an
+    /// error if any count would overflow. The matrix counts the descendants
+    /// of a fish in every state, so this can give up a little earlier than
+    /// `checked_simulate_day` would.
+    pub fn checked_jump_days(&mut self, num_days: u64) -> std::result::Result<(), CountOverflow> {
+        let transition = Transition::<C>::day(&self.rules)
+            .checked_pow(num_days)
+            .ok_or(CountOverflow)?;
+
+        let next = transition
+            .0
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&self.counts)
+                    .try_fold(C::from_u64(0), |total, (&multiplier, &fish)| {
+                        total.checked_add(multiplier.checked_mul(fish)?)
+                    })
+            })
+            .collect::<Option<Vec<C>>>()
+            .ok_or(CountOverflow)?;
+        // ages() and count() add up the states too
+        let total = next
+            .iter()
+            .try_fold(C::from_u64(0), |total, &count| total.checked_add(count));
+        if total.is_none() {
+            return Err(CountOverflow);
+        }
+
+        self.counts = next;
+        Ok(())
+    }
+}
+
+// Square matrix taking the number of fish in each state to the numbers a day later
+struct Transition<C>(Vec<Vec<C>>);
+
+impl<C: FishCount> Transition<C> {
+    fn identity(size: usize) -> Self {
+        let mut matrix = vec![vec![C::from_u64(0); size]; size];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = C::from_u64(1);
+        }
+
+        Self(matrix)
+    }
+
+    fn day(rules: &SpawnRules) -> Self {
+        let size = rules.timers() * rules.lifetimes();
+        let mut matrix = vec![vec![C::from_u64(0); size]; size];
+
+        let moves = (0..size).flat_map(|from| {
+            rules
+                .successors(from)
+                .into_iter()
+                .map(move |(to, multiplier)| (from, to, multiplier))
+        });
+        for (from, to, multiplier) in moves {
+            matrix[to][from] = matrix[to][from] + C::from_u64(multiplier);
+        }
+
+        Self(matrix)
+    }
+
+    fn multiply(&self, other: &Self) -> Self {
+        let size = self.0.len();
+        let mut product = vec![vec![C::from_u64(0); size]; size];
+        for (i, row) in product.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..size).fold(C::from_u64(0), |total, k| {
+                    total + self.0[i][k] * other.0[k][j]
+                });
+            }
+        }
+
+        Self(product)
+    }
+
+    // the product, or `None` if any entry or partial sum overflows
+    fn checked_multiply(&self, other: &Self) -> Option<Self> {
+        let size = self.0.len();
+        let mut product = vec![vec![C::from_u64(0); size]; size];
+        for (i, row) in product.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..size).try_fold(C::from_u64(0), |total, k| {
+                    total.checked_add(self.0[i][k].checked_mul(other.0[k][j])?)
+                })?;
+            }
+        }
+
+        Some(Self(product))
+    }
+
+    fn pow(&self, exponent: u64) -> Self {
+        self.checked_pow_with(exponent, |a, b| Some(a.multiply(b)))
+            .expect("unchecked multiplication always succeeds")
+    }
+
+    fn checked_pow(&self, exponent: u64) -> Option<Self> {
+        self.checked_pow_with(exponent, Self::checked_multiply)
+    }
+
+    // square-and-multiply, squaring only while bits of the exponent are left
+    // so that no power beyond the one asked for is ever computed
+    fn checked_pow_with(
+        &self,
+        mut exponent: u64,
+        multiply: impl Fn(&Self, &Self) -> Option<Self>,
+    ) -> Option<Self> {
+        let mut result = Self::identity(self.0.len());
+        let mut square = Self(self.0.clone());
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = multiply(&result, &square)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                square = multiply(&square, &square)?;
+            }
+        }
+
+        Some(result)
+    }
+}
+
+/// Writes the ages after each day as CSV, with a row per day counting from 1.
+pub fn write_history_csv<W: Write, C: FishCount>(
+    mut writer: W,
+    history: impl IntoIterator<Item = Vec<C>>,
+) -> io::Result<()> {
+    let mut history = history.into_iter().peekable();
+    let timers = history.peek().map_or(0, |ages| ages.len());
+
+    let ages: Vec<String> = (0..timers).map(|age| format!("age{}", age)).collect();
+    writeln!(writer, "day,{},total", ages.join(","))?;
+
+    for (day, ages) in history.enumerate() {
+        let total = ages
+            .iter()
+            .fold(C::from_u64(0), |total, &count| total + count);
+        let ages: Vec<String> = ages.iter().map(|count| count.to_string()).collect();
+        writeln!(writer, "{},{},{}", day + 1, ages.join(","), total)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the ages after each day as a JSON array of
+/// `{"day": 1, "ages": [...], "total": ...}` objects.
+pub fn write_history_json<W: Write, C: FishCount>(
+    mut writer: W,
+    history: impl IntoIterator<Item = Vec<C>>,
+) -> io::Result<()> {
+    write!(writer, "[")?;
+
+    for (day, ages) in history.into_iter().enumerate() {
+        let total = ages
+            .iter()
+            .fold(C::from_u64(0), |total, &count| total + count);
+        let ages: Vec<String> = ages.iter().map(|count| count.to_string()).collect();
+
+        if day > 0 {
+            write!(writer, ",")?;
+        }
+        write!(
+            writer,
+            "\n  {{\"day\": {}, \"ages\": [{}], \"total\": {}}}",
+            day + 1,
+            ages.join(", "),
+            total
+        )?;
+    }
+
+    writeln!(writer, "\n]")
+}
+
+pub fn parse_fish_ages(ages: &str) -> Result<Vec<u8>> {
+    // parse 3,4,3,1,2
+    ages.split(',')
+        .map(|age| {
+            let value = parse_token::<u8>(ages, age, "a fish age")?;
+            if value > 8 {
+                return Err(ParseError::invalid_token(
+                    ages,
+                    age,
+                    "a fish age between 0 and 8",
+                ));
+            }
+
+            Ok(value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod day6_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fish_ages() {
+        let ages = "3,4,3,1,2";
+        let ages = parse_fish_ages(ages).unwrap();
+
+        assert_eq!(vec![3, 4, 3, 1, 2], ages);
+    }
+
+    #[test]
+    fn test_parse_fish_ages_invalid() {
+        let err = parse_fish_ages("3,4,a,1,2").unwrap_err();
+        assert_eq!("1:5: expected a fish age, found `a`", err.to_string());
+
+        let err = parse_fish_ages("3,4,9,1,2").unwrap_err();
+        assert_eq!(
+            "1:5: expected a fish age between 0 and 8, found `9`",
+            err.to_string()
+        );
+    }
+
+    mod fish_tests {
+        use super::*;
+
+        #[test]
+        fn test_simulate_day() {
+            let mut fish = Fish::new(&[3_u8, 4, 3, 1, 2]);
+
+            fish.simulate_day();
+            assert_eq!(5, fish.count());
+
+            fish.simulate_day();
+            assert_eq!(6, fish.count());
+
+            fish.simulate_day();
+            assert_eq!(7, fish.count());
+
+            fish.simulate_day();
+            assert_eq!(9, fish.count());
+        }
+
+        #[test]
+        fn test_simulate_days() {
+            let mut fish = Fish::new(&[3_u8, 4, 3, 1, 2]);
+            fish.simulate_days(18);
+            assert_eq!(26, fish.count());
+
+            let mut fish = Fish::new(&[3_u8, 4, 3, 1, 2]);
+            fish.simulate_days(80);
+            assert_eq!(5934, fish.count());
+
+            let mut fish = Fish::new(&[3_u8, 4, 3, 1, 2]);
+            fish.simulate_days(256);
+            assert_eq!(26984457539, fish.count());
+        }
+
+        #[test]
+        fn test_jump_days() {
+            for days in [0, 1, 2, 7, 8, 9, 18, 80, 256, 300] {
+                let mut simulated = Fish::new(&[3_u8, 4, 3, 1, 2]);
+                simulated.simulate_days(days);
+
+                let mut jumped = Fish::new(&[3_u8, 4, 3, 1, 2]);
+                jumped.jump_days(days as u64);
+
+                assert_eq!(simulated.count(), jumped.count(), "{} days", days);
+            }
+        }
+
+        #[test]
+        fn test_jump_days_u128() {
+            let mut simulated = Fish::<u128>::from_ages(&[3_u8, 4, 3, 1, 2]);
+            simulated.simulate_days(600);
+
+            let mut jumped = Fish::<u128>::from_ages(&[3_u8, 4, 3, 1, 2]);
+            jumped.jump_days(600);
+
+            assert_eq!(simulated.count(), jumped.count());
+            assert!(jumped.count() > u64::MAX as u128);
+        }
+
+        #[test]
+        fn test_jump_days_large_litters() {
+            let rules = SpawnRules {
+                litter_size: 1000,
+                ..SpawnRules::LANTERNFISH
+            };
+
+            let mut simulated = Fish::<u128>::with_rules(&[3_u8, 4, 3, 1, 2], rules).unwrap();
+            simulated.simulate_days(16);
+
+            let mut jumped = Fish::<u128>::with_rules(&[3_u8, 4, 3, 1, 2], rules).unwrap();
+            jumped.jump_days(16);
+
+            assert_eq!(simulated.count(), jumped.count());
+        }
+
+        #[test]
+        fn test_checked_jump_days() {
+            let mut simulated = Fish::<u64>::from_ages(&[3_u8, 4, 3, 1, 2]);
+            simulated.simulate_days(256);
+
+            let mut jumped = Fish::<u64>::from_ages(&[3_u8, 4, 3, 1, 2]);
+            assert_eq!(Ok(()), jumped.checked_jump_days(256));
+            assert_eq!(simulated.count(), jumped.count());
+
+            assert_eq!(Err(CountOverflow), jumped.checked_jump_days(600));
+            assert_eq!(simulated.count(), jumped.count());
+
+            let mut jumped = Fish::<u128>::from_ages(&[3_u8, 4, 3, 1, 2]);
+            assert_eq!(Ok(()), jumped.checked_jump_days(600));
+            assert!(jumped.count() > u64::MAX as u128);
+        }
+
+        #[test]
+        fn test_lanternfish_rules() {
+            let rules = SpawnRules {
+                reset_age: 6,
+                newborn_age: 8,
+                litter_size: 1,
+                max_lifespan: None,
+            };
+
+            let mut fish = Fish::<u64>::with_rules(&[3_u8, 4, 3, 1, 2], rules).unwrap();
+            fish.simulate_days(80);
+            assert_eq!(5934, fish.count());
+
+            fish.jump_days(256 - 80);
+            assert_eq!(26984457539, fish.count());
+        }
+
+        #[test]
+        fn test_litter_size() {
+            let rules = SpawnRules {
+                litter_size: 3,
+                ..SpawnRules::LANTERNFISH
+            };
+
+            let mut fish = Fish::<u64>::with_rules(&[0], rules).unwrap();
+            fish.simulate_day();
+            assert_eq!(4, fish.count());
+            // the first litter is ready 9 days later, and the parent 7
+            fish.simulate_days(7);
+            assert_eq!(7, fish.count());
+            fish.simulate_days(2);
+            assert_eq!(16, fish.count());
+        }
+
+        #[test]
+        fn test_short_cycle() {
+            let rules = SpawnRules {
+                reset_age: 1,
+                newborn_age: 1,
+                litter_size: 1,
+                max_lifespan: None,
+            };
+
+            // a fish at 0 doubles every other day
+            let mut fish = Fish::<u64>::with_rules(&[0], rules).unwrap();
+            fish.simulate_days(10);
+            assert_eq!(32, fish.count());
+        }
+
+        #[test]
+        fn test_max_lifespan() {
+            let rules = SpawnRules {
+                max_lifespan: Some(3),
+                ..SpawnRules::LANTERNFISH
+            };
+
+            // spawns on its first day, then dies of old age before its next litter
+            let mut fish = Fish::<u64>::with_rules(&[0], rules).unwrap();
+            fish.simulate_days(2);
+            assert_eq!(2, fish.count());
+            fish.simulate_day();
+            assert_eq!(1, fish.count());
+            fish.simulate_days(3);
+            assert_eq!(0, fish.count());
+
+            let mut fish = Fish::<u64>::with_rules(&[5], rules).unwrap();
+            fish.simulate_days(3);
+            assert_eq!(0, fish.count());
+        }
+
+        #[test]
+        fn test_invalid_rules() {
+            assert_eq!(
+                Err(SpawnError::NoLifespan),
+                SpawnRules::new(6, 8, 1, Some(0))
+            );
+            assert!(SpawnRules::new(6, 8, 1, Some(1)).is_ok());
+
+            let rules = SpawnRules {
+                max_lifespan: Some(0),
+                ..SpawnRules::LANTERNFISH
+            };
+            let err = Fish::<u64>::with_rules(&[0], rules).err().unwrap();
+            assert_eq!(SpawnError::NoLifespan, err);
+            assert_eq!(
+                "a maximum lifespan of 0 days leaves no fish",
+                err.to_string()
+            );
+        }
+
+        #[test]
+        fn test_age_out_of_range() {
+            let rules = SpawnRules::new(1, 2, 1, None).unwrap();
+
+            let err = Fish::<u64>::with_rules(&[2, 3, 1], rules).err().unwrap();
+            assert_eq!(SpawnError::AgeOutOfRange(3), err);
+            assert_eq!(
+                "no fish can have a timer of 3 under these rules",
+                err.to_string()
+            );
+
+            let mut fish = Fish::<u64>::with_rules(&[2, 0, 1], rules).unwrap();
+            fish.simulate_day();
+            assert_eq!(4, fish.count());
+        }
+
+        #[test]
+        fn test_history() {
+            let mut fish = Fish::new(&[3_u8, 4, 3, 1, 2]);
+            let history: Vec<Vec<u64>> = fish.history().take(3).collect();
+
+            assert_eq!(
+                history,
+                vec![
+                    vec![1, 1, 2, 1, 0, 0, 0, 0, 0],
+                    vec![1, 2, 1, 0, 0, 0, 1, 0, 1],
+                    vec![2, 1, 0, 0, 0, 1, 1, 1, 1],
+                ]
+            );
+            assert_eq!(7, fish.count());
+        }
+
+        #[test]
+        fn test_history_stops_on_overflow() {
+            let mut fish = Fish::new(&[0_u8]);
+            assert_eq!(505, fish.history().count());
+            let mut fish = Fish::<u128>::from_ages(&[0_u8]);
+            assert_eq!(1000, fish.history().take(1000).count());
+
+            // doubling every other day overflows u64 within 128 days
+            let rules = SpawnRules::new(1, 1, 1, None).unwrap();
+            let mut doubling = Fish::<u64>::with_rules(&[0], rules).unwrap();
+            let days = doubling.history().count();
+            assert_eq!(126, days);
+            assert_eq!(1 << 63, doubling.count());
+            assert_eq!(Err(CountOverflow), doubling.checked_simulate_day());
+            assert_eq!(1 << 63, doubling.count());
+        }
+
+        #[test]
+        fn test_write_history_csv() {
+            let mut fish = Fish::new(&[3_u8, 4, 3, 1, 2]);
+            let mut csv = Vec::new();
+            write_history_csv(&mut csv, fish.history().take(2)).unwrap();
+
+            assert_eq!(
+                "day,age0,age1,age2,age3,age4,age5,age6,age7,age8,total
+1,1,1,2,1,0,0,0,0,0,5
+2,1,2,1,0,0,0,1,0,1,6
+",
+                String::from_utf8(csv).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_write_history_json() {
+            let mut fish = Fish::new(&[0_u8]);
+            let mut json = Vec::new();
+            write_history_json(&mut json, fish.history().take(2)).unwrap();
+
+            assert_eq!(
+                r#"[
+  {"day": 1, "ages": [0, 0, 0, 0, 0, 0, 1, 0, 1], "total": 2},
+  {"day": 2, "ages": [0, 0, 0, 0, 0, 1, 0, 1, 0], "total": 2}
+]
+"#,
+                String::from_utf8(json).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_growth_rate() {
+            let rate = SpawnRules::LANTERNFISH.growth_rate();
+
+            // the largest root of x^9 = x^2 + 1
+            assert!((rate - 1.09102).abs() < 1e-4, "{}", rate);
+            assert!((rate.powi(9) - rate.powi(2) - 1.0).abs() < 1e-6);
+
+            // doubles every other day
+            let rules = SpawnRules {
+                reset_age: 1,
+                newborn_age: 1,
+                litter_size: 1,
+                max_lifespan: None,
+            };
+            assert!((rules.growth_rate() - 2_f64.sqrt()).abs() < 1e-6);
+
+            let rules = SpawnRules {
+                max_lifespan: Some(3),
+                ..SpawnRules::LANTERNFISH
+            };
+            assert_eq!(0.0, rules.growth_rate());
+        }
+
+        #[test]
+        fn test_jump_days_with_rules() {
+            let rules = SpawnRules {
+                reset_age: 4,
+                newborn_age: 6,
+                litter_size: 2,
+                max_lifespan: Some(12),
+            };
+
+            for days in [0, 1, 5, 13, 40] {
+                let mut simulated = Fish::<u64>::with_rules(&[3, 4, 3, 1, 2], rules).unwrap();
+                simulated.simulate_days(days);
+
+                let mut jumped = Fish::<u64>::with_rules(&[3, 4, 3, 1, 2], rules).unwrap();
+                jumped.jump_days(days as u64);
+
+                assert_eq!(simulated.count(), jumped.count(), "{} days", days);
+            }
+        }
+
+        #[test]
+        fn test_jump_days_modular() {
+            const P: u64 = 1_000_000_007;
+
+            let mut exact = Fish::<u128>::from_ages(&[3_u8, 4, 3, 1, 2]);
+            exact.jump_days(600);
+
+            let mut modular = Fish::<Modular<P>>::from_ages(&[3_u8, 4, 3, 1, 2]);
+            modular.jump_days(600);
+
+            assert_eq!((exact.count() % P as u128) as u64, modular.count().value());
+        }
+
+        #[test]
+        fn test_jump_days_split() {
+            const P: u64 = 1_000_000_007;
+
+            let mut once = Fish::<Modular<P>>::from_ages(&[3_u8, 4, 3, 1, 2]);
+            once.jump_days(1_000_000_000_000_000_000);
+
+            let mut twice = Fish::<Modular<P>>::from_ages(&[3_u8, 4, 3, 1, 2]);
+            twice.jump_days(500_000_000_000_000_000);
+            twice.jump_days(500_000_000_000_000_000);
+
+            assert_eq!(once.count(), twice.count());
+        }
+    }
+}
@@ -0,0 +1,954 @@
+use std::{cmp, error::Error, fmt};
+
+use crate::{error::parse_token, Day, Fallible, Result};
+
+pub struct Day7;
+
+impl Day for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<isize>;
+    type Output = Fallible<isize, FuelOverflow>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_positions(input.trim())
+    }
+
+    fn part1(positions: &Self::Input) -> Self::Output {
+        Fallible(align_constant(positions).map(|alignment| alignment.fuel))
+    }
+
+    fn part2(positions: &Self::Input) -> Self::Output {
+        Fallible(align_increasing(positions).map(|alignment| alignment.fuel))
+    }
+}
+
+pub fn parse_positions(positions: &str) -> Result<Vec<isize>> {
+    // parse 16,1,2,0,4,2,7,1,2,14
+    positions
+        .split(',')
+        .map(|position| parse_token(positions, position, "a position"))
+        .collect()
+}
+
+/// The fuel needed for a move or an alignment does not fit in an `isize`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct FuelOverflow;
+
+impl fmt::Display for FuelOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the fuel needed is too large to count")
+    }
+}
+
+impl Error for FuelOverflow {}
+
+/// How much fuel a crab burns to move a distance.
+///
+/// The solvers that search for the best position assume the fuel never falls
+/// as the distance grows and rises at a steady or growing rate, i.e. that it is
+/// convex in the distance.
+pub trait FuelModel {
+    /// The fuel the crab at index `crab` burns to move `distance`, or an
+    /// error if it does not fit in an `isize`.
+    fn fuel(&self, crab: usize, distance: isize) -> std::result::Result<isize, FuelOverflow>;
+}
+
+/// Any function or closure of the distance alone is a model.
+impl<F: Fn(isize) -> std::result::Result<isize, FuelOverflow>> FuelModel for F {
+    fn fuel(&self, _crab: usize, distance: isize) -> std::result::Result<isize, FuelOverflow> {
+        self(distance)
+    }
+}
+
+pub fn constant_fuel_burn(distance: isize) -> std::result::Result<isize, FuelOverflow> {
+    Ok(distance)
+}
+
+pub fn increasing_fuel_burn(distance: isize) -> std::result::Result<isize, FuelOverflow> {
+    StepBurn::INCREASING.fuel(0, distance)
+}
+
+/// Burns `first` for the first step and `increment` more for each step after.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct StepBurn {
+    pub first: isize,
+    pub increment: isize,
+}
+
+impl StepBurn {
+    /// The same as `constant_fuel_burn`.
+    pub const CONSTANT: Self = Self {
+        first: 1,
+        increment: 0,
+    };
+
+    /// The same as `increasing_fuel_burn`.
+    pub const INCREASING: Self = Self {
+        first: 1,
+        increment: 1,
+    };
+}
+
+impl FuelModel for StepBurn {
+    fn fuel(&self, _crab: usize, distance: isize) -> std::result::Result<isize, FuelOverflow> {
+        let n = distance as i128;
+        let d = self.increment as i128;
+        let a = self.first as i128;
+
+        // same as (0..distance).map(|step| a + step * d).sum(), and n * (n - 1)
+        // can't overflow an i128
+        let steps = n * (n - 1) / 2;
+        let fuel = steps
+            .checked_mul(d)
+            .and_then(|increments| (n * a).checked_add(increments))
+            .ok_or(FuelOverflow)?;
+
+        isize::try_from(fuel).map_err(|_| FuelOverflow)
+    }
+}
+
+/// Burns `coefficients[0] + coefficients[1] * d + coefficients[2] * d^2 + ...`
+/// for a distance `d`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Polynomial {
+    pub coefficients: Vec<isize>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<isize>) -> Self {
+        Self { coefficients }
+    }
+
+    /// `a * d^2 + b * d + c`
+    pub fn quadratic(a: isize, b: isize, c: isize) -> Self {
+        Self::new(vec![c, b, a])
+    }
+}
+
+impl FuelModel for Polynomial {
+    fn fuel(&self, _crab: usize, distance: isize) -> std::result::Result<isize, FuelOverflow> {
+        self.coefficients
+            .iter()
+            .rev()
+            .try_fold(0_isize, |total, &coefficient| {
+                total.checked_mul(distance)?.checked_add(coefficient)
+            })
+            .ok_or(FuelOverflow)
+    }
+}
+
+/// Scales another model by a weight per crab, so less efficient crabs burn
+/// more. Crabs past the last weight can't move at all: their fuel overflows.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Weighted<M> {
+    model: M,
+    weights: Vec<isize>,
+}
+
+impl<M> Weighted<M> {
+    /// Weights for `crabs` crabs, or an error unless there is exactly one
+    /// weight per crab and none is negative.
+    pub fn new(
+        model: M,
+        weights: Vec<isize>,
+        crabs: usize,
+    ) -> std::result::Result<Self, WeightError> {
+        if weights.len() != crabs {
+            return Err(WeightError::Count {
+                weights: weights.len(),
+                crabs,
+            });
+        }
+        if let Some((crab, &weight)) = weights.iter().enumerate().find(|(_, &w)| w < 0) {
+            return Err(WeightError::Negative { crab, weight });
+        }
+
+        Ok(Self { model, weights })
+    }
+
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    pub fn weights(&self) -> &[isize] {
+        &self.weights
+    }
+}
+
+impl<M: FuelModel> FuelModel for Weighted<M> {
+    fn fuel(&self, crab: usize, distance: isize) -> std::result::Result<isize, FuelOverflow> {
+        let weight = self.weights.get(crab).ok_or(FuelOverflow)?;
+        weight
+            .checked_mul(self.model.fuel(crab, distance)?)
+            .ok_or(FuelOverflow)
+    }
+}
+
+/// Why `Weighted::new` can't weight a set of crabs.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WeightError {
+    /// There are more or fewer weights than crabs.
+    Count { weights: usize, crabs: usize },
+    /// The crab at index `crab` has a negative weight.
+    Negative { crab: usize, weight: isize },
+}
+
+impl fmt::Display for WeightError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WeightError::Count { weights, crabs } => {
+                write!(
+                    f,
+                    "expected {} weights, one per crab, found {}",
+                    crabs, weights
+                )
+            }
+            WeightError::Negative { crab, weight } => {
+                write!(f, "crab {} has a negative weight, {}", crab, weight)
+            }
+        }
+    }
+}
+
+impl Error for WeightError {}
+
+/// Where the crabs line up, and the fuel it takes them to get there.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Alignment<P = isize> {
+    pub position: P,
+    pub fuel: isize,
+}
+
+/// The least fuel needed for any convex `FuelModel`, such as
+/// `constant_fuel_burn` and `increasing_fuel_burn`.
+pub fn find_min_fuel_used<M: FuelModel>(
+    positions: &[isize],
+    model: M,
+) -> std::result::Result<isize, FuelOverflow> {
+    align_convex(positions, model).map(|alignment| alignment.fuel)
+}
+
+/// With a constant burn the median is always a best position.
+pub fn align_constant(positions: &[isize]) -> std::result::Result<Alignment, FuelOverflow> {
+    let mut sorted = positions.to_vec();
+    sorted.sort_unstable();
+    let median = sorted[(sorted.len() - 1) / 2];
+
+    alignment_at(median, total_fuel(positions, median, &StepBurn::CONSTANT))
+}
+
+/// With an increasing burn the best position is within half a step of the
+/// mean, so only the positions either side of it need checking.
+pub fn align_increasing(positions: &[isize]) -> std::result::Result<Alignment, FuelOverflow> {
+    let sum: i128 = positions.iter().map(|&position| position as i128).sum();
+    // the mean of isizes is an isize, and the best position is between the crabs
+    let mean = sum.div_euclid(positions.len() as i128) as isize;
+    let low = cmp::max(mean.saturating_sub(1), *positions.iter().min().unwrap());
+    let high = cmp::min(mean.saturating_add(1), *positions.iter().max().unwrap());
+
+    let (position, fuel) = (low..=high)
+        .map(|target| (target, total_fuel(positions, target, &StepBurn::INCREASING)))
+        .min_by_key(|&(_, fuel)| fuel)
+        .unwrap();
+
+    alignment_at(position, fuel)
+}
+
+/// Ternary search for the best position, which finds it whenever the total
+/// fuel falls and then rises as the target moves along, as it does for any
+/// convex `FuelModel`.
+pub fn align_convex<M: FuelModel>(
+    positions: &[isize],
+    model: M,
+) -> std::result::Result<Alignment, FuelOverflow> {
+    let low = *positions.iter().min().unwrap();
+    let high = *positions.iter().max().unwrap();
+    let reaches = reaches(positions.len(), &model, high.abs_diff(low)).ok_or(FuelOverflow)?;
+
+    // where every crab's fuel fits, the total is exact and so convex
+    let (low, high) = positions.iter().zip(&reaches).fold(
+        (low as i128, high as i128),
+        |(low, high), (&position, &reach)| {
+            (
+                low.max(position as i128 - reach as i128),
+                high.min(position as i128 + reach as i128),
+            )
+        },
+    );
+    if low > high {
+        return Err(FuelOverflow);
+    }
+
+    let (position, fuel) = ternary_search(low as isize, high as isize, |target| {
+        total_fuel(positions, target, &model)
+    });
+
+    alignment_at(position, fuel)
+}
+
+/// Checks every position from the leftmost crab to the rightmost, which
+/// works for any model at all.
+pub fn align_exhaustive<M: FuelModel>(
+    positions: &[isize],
+    model: M,
+) -> std::result::Result<Alignment, FuelOverflow> {
+    let min_position = *positions.iter().min().unwrap();
+    let max_position = *positions.iter().max().unwrap();
+
+    let (position, fuel) = (min_position..=max_position)
+        .map(|target| (target, total_fuel(positions, target, &model)))
+        .min_by_key(|&(_, fuel)| fuel)
+        .unwrap();
+
+    alignment_at(position, fuel)
+}
+
+/// The best point for crabs that move on a plane, where a move costs the
+/// fuel for its Manhattan distance. Searches one axis inside the other, which
+/// finds the best point for any convex `FuelModel`.
+pub fn align_manhattan<M: FuelModel>(
+    positions: &[(isize, isize)],
+    model: M,
+) -> std::result::Result<Alignment<(isize, isize)>, FuelOverflow> {
+    let xs = positions.iter().map(|&(x, _)| x);
+    let ys = positions.iter().map(|&(_, y)| y);
+    let (min_x, max_x) = (xs.clone().min().unwrap(), xs.max().unwrap());
+    let (min_y, max_y) = (ys.clone().min().unwrap(), ys.max().unwrap());
+    let span = max_x.abs_diff(min_x).saturating_add(max_y.abs_diff(min_y));
+    let reaches = reaches(positions.len(), &model, span).ok_or(FuelOverflow)?;
+    let crabs = || {
+        positions
+            .iter()
+            .zip(&reaches)
+            .map(|(&(x, y), &reach)| (x as i128, y as i128, reach as i128))
+    };
+
+    // Every crab reaches a diamond, a square in the coordinates u = x + y
+    // and v = x - y. Their intersection holds the points with u and v in
+    // ranges, and x = (u + v) / 2 for any of them with an even u + v. Moving
+    // a point into the crabs' bounding box only brings it closer to them.
+    let (u_low, u_high, v_low, v_high) = crabs().fold(
+        (i128::MIN, i128::MAX, i128::MIN, i128::MAX),
+        |(u_low, u_high, v_low, v_high), (x, y, reach)| {
+            (
+                u_low.max(x + y - reach),
+                u_high.min(x + y + reach),
+                v_low.max(x - y - reach),
+                v_high.min(x - y + reach),
+            )
+        },
+    );
+    let low_x = (u_low + v_low + 1).div_euclid(2).max(min_x as i128);
+    let high_x = (u_high + v_high).div_euclid(2).min(max_x as i128);
+    if u_low > u_high || v_low > v_high || low_x > high_x {
+        return Err(FuelOverflow);
+    }
+
+    // for an x in range, the ys every crab still reaches
+    let best_y = |x: isize| {
+        let (low, high) = crabs().fold(
+            (min_y as i128, max_y as i128),
+            |(low, high), (crab_x, crab_y, reach)| {
+                let reach = reach - (x as i128 - crab_x).abs();
+                (low.max(crab_y - reach), high.min(crab_y + reach))
+            },
+        );
+
+        ternary_search(low as isize, high as isize, |y| {
+            total_fuel_to_point(positions, (x, y), &model)
+        })
+    };
+
+    let (x, _) = ternary_search(low_x as isize, high_x as isize, |x| best_y(x).1);
+    let (y, fuel) = best_y(x);
+
+    Ok(Alignment {
+        position: (x, y),
+        fuel: isize::try_from(fuel).map_err(|_| FuelOverflow)?,
+    })
+}
+
+fn alignment_at(position: isize, fuel: i128) -> std::result::Result<Alignment, FuelOverflow> {
+    Ok(Alignment {
+        position,
+        fuel: isize::try_from(fuel).map_err(|_| FuelOverflow)?,
+    })
+}
+
+// The lowest point of a function over low..=high, and its value, for a
+// function that only falls and then rises
+fn ternary_search(mut low: isize, mut high: isize, f: impl Fn(isize) -> i128) -> (isize, i128) {
+    while high.abs_diff(low) > 2 {
+        // a third of the gap fits in an isize even when the gap doesn't
+        let third = (high.abs_diff(low) / 3) as isize;
+        let (left, right) = (low + third, high - third);
+
+        match f(left).cmp(&f(right)) {
+            cmp::Ordering::Less => high = right - 1,
+            cmp::Ordering::Greater => low = left + 1,
+            // a minimum lies between two equal points on a convex curve
+            cmp::Ordering::Equal => (low, high) = (left, right),
+        }
+    }
+
+    (low..=high)
+        .map(|x| (x, f(x)))
+        .min_by_key(|&(_, value)| value)
+        .unwrap()
+}
+
+// How far, up to `span`, each crab can move before its fuel overflows, or
+// `None` if some crab can't even stay put. The fuel never falls as the
+// distance grows, so a crab can move any distance up to its reach.
+fn reaches<M: FuelModel>(crabs: usize, model: &M, span: usize) -> Option<Vec<usize>> {
+    (0..crabs)
+        .map(|crab| {
+            model.fuel(crab, 0).ok()?;
+
+            let (mut low, mut high) = (0, isize::try_from(span).unwrap_or(isize::MAX));
+            while low < high {
+                let mid = high - (high - low) / 2;
+                match model.fuel(crab, mid) {
+                    Ok(_) => low = mid,
+                    Err(_) => high = mid - 1,
+                }
+            }
+
+            Some(low as usize)
+        })
+        .collect()
+}
+
+// The fuel for every crab to reach `target`, widened so that the total can't
+// overflow, and i128::MAX if any single crab's fuel does. The cap flattens
+// the total far from the crabs, where a search can't tell which way the
+// minimum lies, so the searches keep to positions every crab can reach.
+fn total_fuel<M: FuelModel>(positions: &[isize], target: isize, model: &M) -> i128 {
+    positions
+        .iter()
+        .enumerate()
+        .map(|(crab, position)| crab_fuel(model, crab, position.abs_diff(target)))
+        .fold(0, i128::saturating_add)
+}
+
+fn total_fuel_to_point<M: FuelModel>(
+    positions: &[(isize, isize)],
+    (x, y): (isize, isize),
+    model: &M,
+) -> i128 {
+    positions
+        .iter()
+        .enumerate()
+        .map(|(crab, (crab_x, crab_y))| {
+            let distance = crab_x.abs_diff(x).checked_add(crab_y.abs_diff(y));
+            distance.map_or(i128::MAX, |distance| crab_fuel(model, crab, distance))
+        })
+        .fold(0, i128::saturating_add)
+}
+
+fn crab_fuel<M: FuelModel>(model: &M, crab: usize, distance: usize) -> i128 {
+    isize::try_from(distance)
+        .ok()
+        .and_then(|distance| model.fuel(crab, distance).ok())
+        .map_or(i128::MAX, |fuel| fuel as i128)
+}
+
+pub fn sum_total_fuel_used_to_position<M: FuelModel>(
+    positions: &[isize],
+    target: isize,
+    model: &M,
+) -> std::result::Result<isize, FuelOverflow> {
+    isize::try_from(total_fuel(positions, target, model)).map_err(|_| FuelOverflow)
+}
+
+pub fn sum_total_fuel_used_to_point<M: FuelModel>(
+    positions: &[(isize, isize)],
+    point: (isize, isize),
+    model: &M,
+) -> std::result::Result<isize, FuelOverflow> {
+    isize::try_from(total_fuel_to_point(positions, point, model)).map_err(|_| FuelOverflow)
+}
+
+#[cfg(test)]
+mod day7_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_positions() {
+        let positions = "16,1,2,0,4,2,7,1,2,14";
+        let positions = parse_positions(positions).unwrap();
+
+        assert_eq!(vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14], positions);
+    }
+
+    #[test]
+    fn test_parse_positions_invalid() {
+        let err = parse_positions("16,1,2;0,4").unwrap_err();
+        assert_eq!("1:6: expected a position, found `2;0`", err.to_string());
+    }
+
+    #[test]
+    fn test_sum_total_fuel_used_to_position_constant_fuel_burn() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let target = 2;
+
+        let total = sum_total_fuel_used_to_position(&positions, target, &constant_fuel_burn);
+        assert_eq!(Ok(37), total);
+    }
+
+    #[test]
+    fn test_sum_total_fuel_used_to_position_increasing_fuel_burn() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+        let target = 2;
+        let total = sum_total_fuel_used_to_position(&positions, target, &increasing_fuel_burn);
+        assert_eq!(Ok(206), total);
+
+        let target = 5;
+        let total = sum_total_fuel_used_to_position(&positions, target, &increasing_fuel_burn);
+        assert_eq!(Ok(168), total);
+    }
+
+    #[test]
+    fn test_find_min_fuel_used_constant_fuel_burn() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+        let fuel_used = find_min_fuel_used(&positions, constant_fuel_burn);
+        assert_eq!(Ok(37), fuel_used);
+    }
+
+    #[test]
+    fn test_find_min_fuel_used_increasing_fuel_burn() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+        let fuel_used = find_min_fuel_used(&positions, increasing_fuel_burn);
+        assert_eq!(Ok(168), fuel_used);
+    }
+
+    #[test]
+    fn test_align_sample() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+        assert_eq!(
+            Ok(Alignment {
+                position: 2,
+                fuel: 37
+            }),
+            align_constant(&positions)
+        );
+        assert_eq!(
+            Ok(Alignment {
+                position: 5,
+                fuel: 168
+            }),
+            align_increasing(&positions)
+        );
+        assert_eq!(
+            align_constant(&positions),
+            align_convex(&positions, constant_fuel_burn)
+        );
+        assert_eq!(
+            align_increasing(&positions),
+            align_convex(&positions, increasing_fuel_burn)
+        );
+    }
+
+    #[test]
+    fn test_align_matches_exhaustive() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        type Burn = fn(isize) -> std::result::Result<isize, FuelOverflow>;
+
+        fn squared_fuel_burn(distance: isize) -> std::result::Result<isize, FuelOverflow> {
+            distance.checked_mul(distance).ok_or(FuelOverflow)
+        }
+
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for _ in 0..200 {
+            let count = rng.gen_range(1..40);
+            let positions: Vec<isize> = (0..count).map(|_| rng.gen_range(-300..300)).collect();
+
+            let check = |alignment: Alignment, calculate_fuel_required: Burn| {
+                let best = align_exhaustive(&positions, calculate_fuel_required).unwrap();
+                assert_eq!(best.fuel, alignment.fuel, "{:?}", positions);
+                assert_eq!(
+                    Ok(alignment.fuel),
+                    sum_total_fuel_used_to_position(
+                        &positions,
+                        alignment.position,
+                        &calculate_fuel_required
+                    )
+                );
+            };
+
+            check(align_constant(&positions).unwrap(), constant_fuel_burn);
+            check(align_increasing(&positions).unwrap(), increasing_fuel_burn);
+            check(
+                align_convex(&positions, constant_fuel_burn).unwrap(),
+                constant_fuel_burn,
+            );
+            check(
+                align_convex(&positions, increasing_fuel_burn).unwrap(),
+                increasing_fuel_burn,
+            );
+            check(
+                align_convex(&positions, squared_fuel_burn).unwrap(),
+                squared_fuel_burn,
+            );
+        }
+    }
+
+    #[test]
+    fn test_models() {
+        assert_eq!(increasing_fuel_burn.fuel(0, 11), Ok(66));
+        assert_eq!(
+            StepBurn {
+                first: 1,
+                increment: 1
+            }
+            .fuel(0, 11),
+            Ok(66)
+        );
+        assert_eq!(
+            StepBurn {
+                first: 3,
+                increment: 2
+            }
+            .fuel(0, 4),
+            Ok(3 + 5 + 7 + 9)
+        );
+        assert_eq!(Polynomial::quadratic(2, 3, 1).fuel(0, 5), Ok(66));
+        assert_eq!(Polynomial::new(vec![0, 0, 0, 1]).fuel(0, 3), Ok(27));
+
+        let weighted = Weighted::new(constant_fuel_burn, vec![1, 10], 2).unwrap();
+        assert_eq!(weighted.fuel(0, 4), Ok(4));
+        assert_eq!(weighted.fuel(1, 4), Ok(40));
+    }
+
+    #[test]
+    fn test_align_with_models() {
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+
+        // a closure capturing its parameters
+        let rate = 3;
+        assert_eq!(
+            Ok(Alignment {
+                position: 2,
+                fuel: 37 * 3
+            }),
+            align_convex(&positions, |distance| Ok(rate * distance))
+        );
+
+        let model = StepBurn {
+            first: 1,
+            increment: 1,
+        };
+        assert_eq!(
+            align_increasing(&positions),
+            align_convex(&positions, model)
+        );
+
+        // one crab so inefficient that everyone else comes to it
+        let mut weights = vec![1; positions.len()];
+        weights[0] = 100;
+        let weighted = Weighted::new(constant_fuel_burn, weights, positions.len()).unwrap();
+        assert_eq!(
+            Ok(Alignment {
+                position: 16,
+                fuel: 111
+            }),
+            align_convex(&positions, weighted)
+        );
+    }
+
+    #[test]
+    fn test_align_manhattan() {
+        let positions = vec![(0, 0), (4, 0), (0, 4), (1, 1), (1, 1)];
+
+        let alignment = align_manhattan(&positions, constant_fuel_burn).unwrap();
+        assert_eq!(alignment.position, (1, 1));
+        assert_eq!(alignment.fuel, 2 + 4 + 4);
+
+        // on a line the plane is no different
+        let on_line: Vec<(isize, isize)> = [16, 1, 2, 0, 4, 2, 7, 1, 2, 14]
+            .iter()
+            .map(|&x| (x, 3))
+            .collect();
+        let alignment = align_manhattan(&on_line, increasing_fuel_burn).unwrap();
+        assert_eq!(alignment.position, (5, 3));
+        assert_eq!(alignment.fuel, 168);
+    }
+
+    #[test]
+    fn test_align_manhattan_matches_exhaustive() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(19);
+
+        for _ in 0..50 {
+            let count = rng.gen_range(1..15);
+            let positions: Vec<(isize, isize)> = (0..count)
+                .map(|_| (rng.gen_range(-20..20), rng.gen_range(-20..20)))
+                .collect();
+
+            for model in [constant_fuel_burn, increasing_fuel_burn] {
+                let best = (-20..20)
+                    .flat_map(|x| (-20..20).map(move |y| (x, y)))
+                    .map(|point| sum_total_fuel_used_to_point(&positions, point, &model).unwrap())
+                    .min()
+                    .unwrap();
+
+                let alignment = align_manhattan(&positions, model).unwrap();
+                assert_eq!(best, alignment.fuel, "{:?}", positions);
+                assert_eq!(
+                    Ok(alignment.fuel),
+                    sum_total_fuel_used_to_point(&positions, alignment.position, &model)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_constant_fuel_burn() {
+        assert_eq!(Ok(3), constant_fuel_burn(3));
+    }
+
+    #[test]
+    fn test_increasing_fuel_burn() {
+        assert_eq!(Ok(5050), increasing_fuel_burn(100));
+        assert_eq!(Ok(10), increasing_fuel_burn(4));
+        assert_eq!(Ok(55), increasing_fuel_burn(10));
+        assert_eq!(Ok(15), increasing_fuel_burn(5));
+    }
+
+    #[test]
+    fn test_checked_models() {
+        assert_eq!(Err(FuelOverflow), StepBurn::INCREASING.fuel(0, isize::MAX));
+        assert_eq!(Ok(isize::MAX), StepBurn::CONSTANT.fuel(0, isize::MAX));
+        assert_eq!(
+            Err(FuelOverflow),
+            Polynomial::quadratic(1, 0, 0).fuel(0, 1 << 32)
+        );
+        assert_eq!(Ok(1 << 62), Polynomial::quadratic(1, 0, 0).fuel(0, 1 << 31));
+
+        let weighted = Weighted::new(StepBurn::CONSTANT, vec![2], 1).unwrap();
+        assert_eq!(Err(FuelOverflow), weighted.fuel(0, isize::MAX));
+        // no weight, so no crab to move
+        assert_eq!(Err(FuelOverflow), weighted.fuel(1, 1));
+    }
+
+    #[test]
+    fn test_weighted_new_invalid() {
+        let err = Weighted::new(StepBurn::CONSTANT, vec![1, 2], 3).unwrap_err();
+        assert_eq!(
+            WeightError::Count {
+                weights: 2,
+                crabs: 3
+            },
+            err
+        );
+        assert_eq!("expected 3 weights, one per crab, found 2", err.to_string());
+
+        let err = Weighted::new(StepBurn::CONSTANT, vec![1, 0, -2], 3).unwrap_err();
+        assert_eq!(
+            WeightError::Negative {
+                crab: 2,
+                weight: -2
+            },
+            err
+        );
+        assert_eq!("crab 2 has a negative weight, -2", err.to_string());
+
+        let positions = vec![1, 5];
+        let weighted = Weighted::new(StepBurn::CONSTANT, vec![1, 3], positions.len()).unwrap();
+        assert_eq!(&[1, 3], weighted.weights());
+        assert_eq!(
+            Ok(Alignment {
+                position: 5,
+                fuel: 4
+            }),
+            align_convex(&positions, weighted)
+        );
+    }
+
+    #[test]
+    fn test_increasing_fuel_burn_overflow() {
+        assert_eq!(Err(FuelOverflow), increasing_fuel_burn(1 << 33));
+    }
+
+    #[test]
+    fn test_parts_report_overflow() {
+        let positions = vec![isize::MIN, isize::MAX];
+
+        assert_eq!(Fallible(Err(FuelOverflow)), Day7::part1(&positions));
+        assert_eq!(Fallible(Err(FuelOverflow)), Day7::part2(&positions));
+        assert_eq!(
+            "error: the fuel needed is too large to count",
+            Day7::part1(&positions).to_string()
+        );
+
+        let positions = vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        assert_eq!("37", Day7::part1(&positions).to_string());
+        assert_eq!("168", Day7::part2(&positions).to_string());
+    }
+
+    #[test]
+    fn test_align_extreme_positions() {
+        let positions = vec![isize::MIN, isize::MAX];
+        assert_eq!(Err(FuelOverflow), align_constant(&positions));
+        assert_eq!(Err(FuelOverflow), align_increasing(&positions));
+        assert_eq!(
+            Err(FuelOverflow),
+            align_convex(&positions, StepBurn::INCREASING)
+        );
+        assert_eq!(
+            Err(FuelOverflow),
+            sum_total_fuel_used_to_position(&positions, 0, &StepBurn::CONSTANT)
+        );
+
+        // far from zero, but close together
+        let positions = vec![isize::MAX, isize::MAX - 4, isize::MAX - 1];
+        let expected = Ok(Alignment {
+            position: isize::MAX - 1,
+            fuel: 4,
+        });
+        assert_eq!(expected, align_constant(&positions));
+        assert_eq!(expected, align_convex(&positions, StepBurn::CONSTANT));
+
+        let positions = vec![isize::MIN, isize::MIN + 6];
+        assert_eq!(
+            Ok(Alignment {
+                position: isize::MIN + 3,
+                fuel: 12,
+            }),
+            align_increasing(&positions)
+        );
+    }
+
+    #[test]
+    fn test_align_where_some_crabs_overflow() {
+        // the heavy crab overflows more than 900,000 steps from home
+        let heavy = isize::MAX / 900_000;
+
+        let positions = vec![0, 3_000_000];
+        let weighted = Weighted::new(StepBurn::CONSTANT, vec![1, heavy], 2).unwrap();
+        assert_eq!(
+            Ok(Alignment {
+                position: 3_000_000,
+                fuel: 3_000_000
+            }),
+            align_convex(&positions, weighted)
+        );
+
+        let weighted = Weighted::new(StepBurn::CONSTANT, vec![heavy, heavy], 2).unwrap();
+        assert_eq!(Err(FuelOverflow), align_convex(&positions, weighted));
+
+        let positions = vec![(0, 0), (1_500_000, 1_500_000)];
+        let weighted = Weighted::new(StepBurn::CONSTANT, vec![1, heavy], 2).unwrap();
+        assert_eq!(
+            Ok(Alignment {
+                position: (1_500_000, 1_500_000),
+                fuel: 3_000_000
+            }),
+            align_manhattan(&positions, weighted)
+        );
+    }
+
+    #[test]
+    fn test_align_overflowing_matches_exhaustive() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(21);
+
+        for _ in 0..200 {
+            let count = rng.gen_range(1..8);
+            // heavy crabs overflow more than 15 steps from home
+            let weights: Vec<isize> = (0..count)
+                .map(|_| [1, isize::MAX / 15][rng.gen_range(0..2)])
+                .collect();
+            let model = Weighted::new(StepBurn::CONSTANT, weights, count).unwrap();
+
+            let positions: Vec<isize> = (0..count).map(|_| rng.gen_range(-20..20)).collect();
+            let best = (-20..20)
+                .filter_map(|target| {
+                    sum_total_fuel_used_to_position(&positions, target, &model).ok()
+                })
+                .min();
+            assert_eq!(
+                best,
+                align_convex(&positions, model.clone())
+                    .ok()
+                    .map(|alignment| alignment.fuel),
+                "{:?}",
+                positions
+            );
+
+            let positions: Vec<(isize, isize)> = (0..count)
+                .map(|_| (rng.gen_range(-20..20), rng.gen_range(-20..20)))
+                .collect();
+            let best = (-20..20)
+                .flat_map(|x| (-20..20).map(move |y| (x, y)))
+                .filter_map(|point| sum_total_fuel_used_to_point(&positions, point, &model).ok())
+                .min();
+            assert_eq!(
+                best,
+                align_manhattan(&positions, model.clone())
+                    .ok()
+                    .map(|alignment| alignment.fuel),
+                "{:?}",
+                positions
+            );
+        }
+    }
+
+    #[test]
+    fn test_align_large_positions() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(20);
+
+        // big enough that some alignments overflow an isize and some don't
+        for _ in 0..200 {
+            let count = rng.gen_range(1..20);
+            let spread = 1 << rng.gen_range(1..34);
+            let positions: Vec<isize> = (0..count)
+                .map(|_| rng.gen_range(-spread..=spread))
+                .collect();
+
+            let exact = |target: isize| -> i128 {
+                positions
+                    .iter()
+                    .map(|position| {
+                        let n = position.abs_diff(target) as i128;
+                        n * (n + 1) / 2
+                    })
+                    .sum()
+            };
+
+            let alignment = align_increasing(&positions);
+            assert_eq!(
+                alignment,
+                align_convex(&positions, StepBurn::INCREASING),
+                "{:?}",
+                positions
+            );
+
+            if let Ok(alignment) = alignment {
+                let position = alignment.position;
+                assert_eq!(alignment.fuel as i128, exact(position));
+                assert!(exact(position - 1) >= exact(position));
+                assert!(exact(position + 1) >= exact(position));
+            } else {
+                let best = (-spread..=spread)
+                    .step_by(spread as usize / 4)
+                    .map(exact)
+                    .chain([exact(positions[0])])
+                    .min()
+                    .unwrap();
+                assert!(best > isize::MAX as i128, "{:?}", positions);
+            }
+        }
+    }
+}
@@ -0,0 +1,1740 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    error::{next_token, parse_token},
+    Day, Fallible, ParseError, Result,
+};
+
+/// The ten signal patterns and four output digits from one line of the notes.
+pub type Entry = (Vec<Segments>, Vec<Segments>);
+
+/// How many output patterns there are on each line of the notes.
+pub const OUTPUTS: usize = 4;
+
+pub struct Day8;
+
+impl Day for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Vec<Entry>;
+    type Output = Fallible<usize, DecodeError>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                parse_input_signals_and_output_digits(line).map_err(|err| err.offset_lines(i))
+            })
+            .collect()
+    }
+
+    fn part1(entries: &Self::Input) -> Self::Output {
+        let count = entries
+            .iter()
+            .flat_map(|(_, output_digits)| output_digits)
+            .filter(|digit| is_unique_segment_count(digit.len()))
+            .count();
+
+        Fallible(Ok(count))
+    }
+
+    fn part2(entries: &Self::Input) -> Self::Output {
+        let mut sum: usize = 0;
+
+        for (input_digits, output_digits) in entries {
+            let input = determine_input_numbers(input_digits);
+            match determine_output_number(&input, output_digits) {
+                Ok(number) => sum += number,
+                Err(err) => return Fallible(Err(err)),
+            }
+        }
+
+        Fallible(Ok(sum))
+    }
+}
+
+/// A set of the segments, or wires, `a` to `p`, one bit each from `a` up. The
+/// puzzle's displays only use `a` to `g`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Segments(pub u16);
+
+impl Segments {
+    pub const NONE: Self = Self(0);
+
+    /// The first `count` letters, from `a`.
+    pub const fn first(count: usize) -> Self {
+        Self(((1_u32 << count) - 1) as u16)
+    }
+
+    /// The set of the given letters, leaving out any but `a` to `p`.
+    pub const fn from_letters(letters: &str) -> Self {
+        let letters = letters.as_bytes();
+        let mut bits = 0;
+        let mut i = 0;
+        while i < letters.len() {
+            if b'a' <= letters[i] && letters[i] <= b'p' {
+                bits |= 1 << (letters[i] - b'a');
+            }
+            i += 1;
+        }
+
+        Self(bits)
+    }
+
+    /// The set of just `letter`, or `None` if it is not `a` to `p`.
+    pub fn single(letter: char) -> Option<Self> {
+        letter_index(letter).map(|index| Self(1 << index))
+    }
+
+    /// How many segments are lit.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, letter: char) -> bool {
+        Self::single(letter).is_some_and(|single| self.0 & single.0 != 0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// The letters in the set, in order.
+    pub fn letters(self) -> impl Iterator<Item = char> + Clone {
+        ('a'..='p').filter(move |&letter| self.contains(letter))
+    }
+}
+
+/// Letters other than `a` to `p` are left out.
+impl FromIterator<char> for Segments {
+    fn from_iter<I: IntoIterator<Item = char>>(letters: I) -> Self {
+        letters
+            .into_iter()
+            .filter_map(Self::single)
+            .fold(Self::NONE, Self::union)
+    }
+}
+
+impl FromStr for Segments {
+    type Err = ParseError;
+
+    fn from_str(pattern: &str) -> Result<Self> {
+        // parse "cfbegad"
+        if pattern.is_empty() || !pattern.chars().all(|letter| ('a'..='p').contains(&letter)) {
+            return Err(ParseError::invalid_token(
+                pattern,
+                pattern,
+                "a signal pattern of the letters a to p",
+            ));
+        }
+
+        Ok(pattern.chars().collect())
+    }
+}
+
+impl fmt::Display for Segments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.letters()
+            .try_for_each(|letter| write!(f, "{}", letter))
+    }
+}
+
+// the bit for a segment, or `None` for anything other than `a` to `p`
+fn letter_index(letter: char) -> Option<usize> {
+    ('a'..='p')
+        .contains(&letter)
+        .then(|| letter as usize - 'a' as usize)
+}
+
+pub fn is_unique_segment_count(num_segments: usize) -> bool {
+    // 1 has 2 segments
+    // 4 has 4 segments
+    // 7 has 3 segments
+    // 8 has 7 segments
+    Alphabet::DECIMAL.has_unique_size(num_segments)
+}
+
+pub fn parse_input_signals_and_output_digits(input: &str) -> Result<Entry> {
+    Alphabet::DECIMAL.parse_entry(input)
+}
+
+pub fn determine_input_numbers(input: &[Segments]) -> Vec<Segments> {
+    let mut numbers = vec![Segments::NONE; 10];
+
+    for &some_num in input {
+        match some_num.len() {
+            2 => numbers[1] = some_num,
+            3 => numbers[7] = some_num,
+            4 => numbers[4] = some_num,
+            7 => numbers[8] = some_num,
+            _ => (),
+        };
+    }
+
+    for &some_num in input {
+        if is_zero(numbers[1], numbers[4], some_num) {
+            numbers[0] = some_num;
+        } else if is_two(numbers[4], some_num) {
+            numbers[2] = some_num;
+        } else if is_three(numbers[1], some_num) {
+            numbers[3] = some_num;
+        } else if is_five(numbers[1], numbers[4], some_num) {
+            numbers[5] = some_num;
+        } else if is_six(numbers[1], some_num) {
+            numbers[6] = some_num;
+        } else if is_nine(numbers[4], some_num) {
+            numbers[9] = some_num;
+        }
+    }
+
+    numbers
+}
+
+/// The number the output digits read, or the first digit that isn't one of
+/// the patterns `determine_input_numbers` found.
+pub fn determine_output_number(
+    input: &[Segments],
+    output_digits: &[Segments],
+) -> std::result::Result<usize, DecodeError> {
+    output_digits.iter().try_fold(0, |number, &digit| {
+        let index = input
+            .iter()
+            .position(|&input_num| input_num == digit && !digit.is_empty())
+            .ok_or(DecodeError::UnknownPattern(digit))?;
+        Ok(number * 10 + index)
+    })
+}
+
+fn is_zero(one: Segments, four: Segments, some_num: Segments) -> bool {
+    some_num.len() == 6
+        && one.intersection(some_num).len() == one.len()
+        && four.intersection(some_num).len() == 3
+}
+
+fn is_two(four: Segments, some_num: Segments) -> bool {
+    some_num.len() == 5 && four.intersection(some_num).len() == 2
+}
+
+fn is_three(one: Segments, some_num: Segments) -> bool {
+    some_num.len() == 5 && one.intersection(some_num).len() == one.len()
+}
+
+fn is_five(one: Segments, four: Segments, some_num: Segments) -> bool {
+    some_num.len() == 5
+        && one.intersection(some_num).len() == 1
+        && four.intersection(some_num).len() == 3
+}
+
+fn is_six(one: Segments, some_num: Segments) -> bool {
+    some_num.len() == 6 && one.intersection(some_num).len() == 1
+}
+
+fn is_nine(four: Segments, some_num: Segments) -> bool {
+    some_num.len() == 6 && four.intersection(some_num).len() == four.len()
+}
+
+/// The segments lit for each digit on an unscrambled display, where `a` is the
+/// top segment, `b` and `c` the upper left and right, `d` the middle, `e` and
+/// `f` the lower left and right, and `g` the bottom.
+pub const DIGITS: [Segments; 10] = [
+    Segments::from_letters("abcefg"),
+    Segments::from_letters("cf"),
+    Segments::from_letters("acdeg"),
+    Segments::from_letters("acdfg"),
+    Segments::from_letters("bcdf"),
+    Segments::from_letters("abdfg"),
+    Segments::from_letters("abdefg"),
+    Segments::from_letters("acf"),
+    Segments::from_letters("abcdefg"),
+    Segments::from_letters("abcdfg"),
+];
+
+const DECIMAL_SYMBOLS: [(char, Segments); 10] = [
+    ('0', DIGITS[0]),
+    ('1', DIGITS[1]),
+    ('2', DIGITS[2]),
+    ('3', DIGITS[3]),
+    ('4', DIGITS[4]),
+    ('5', DIGITS[5]),
+    ('6', DIGITS[6]),
+    ('7', DIGITS[7]),
+    ('8', DIGITS[8]),
+    ('9', DIGITS[9]),
+];
+
+const HEXADECIMAL_SYMBOLS: [(char, Segments); 16] = [
+    ('0', DIGITS[0]),
+    ('1', DIGITS[1]),
+    ('2', DIGITS[2]),
+    ('3', DIGITS[3]),
+    ('4', DIGITS[4]),
+    ('5', DIGITS[5]),
+    ('6', DIGITS[6]),
+    ('7', DIGITS[7]),
+    ('8', DIGITS[8]),
+    ('9', DIGITS[9]),
+    ('A', Segments::from_letters("abcdef")),
+    ('b', Segments::from_letters("bdefg")),
+    ('C', Segments::from_letters("abeg")),
+    ('d', Segments::from_letters("cdefg")),
+    ('E', Segments::from_letters("abdeg")),
+    ('F', Segments::from_letters("abde")),
+];
+
+// `a` is the top segment, `b` and `c` the upper left and right, `d` and `e`
+// the middle left and right, `f` and `g` the lower left and right, and `h` the
+// bottom. `i`, `j` and `k` run from the centre up to the left, straight up and
+// up to the right, and `l`, `m` and `n` down to the left, straight down and
+// down to the right.
+const ALPHANUMERIC_SYMBOLS: [(char, Segments); 36] = [
+    ('0', Segments::from_letters("abcfghkl")),
+    ('1', Segments::from_letters("cg")),
+    ('2', Segments::from_letters("acdefh")),
+    ('3', Segments::from_letters("acegh")),
+    ('4', Segments::from_letters("bcdeg")),
+    ('5', Segments::from_letters("abdegh")),
+    ('6', Segments::from_letters("abdefgh")),
+    ('7', Segments::from_letters("acg")),
+    ('8', Segments::from_letters("abcdefgh")),
+    ('9', Segments::from_letters("abcdegh")),
+    ('A', Segments::from_letters("abcdefg")),
+    ('B', Segments::from_letters("aceghjm")),
+    ('C', Segments::from_letters("abfh")),
+    ('D', Segments::from_letters("acghjm")),
+    ('E', Segments::from_letters("abdfh")),
+    ('F', Segments::from_letters("abdf")),
+    ('G', Segments::from_letters("abefgh")),
+    ('H', Segments::from_letters("bcdefg")),
+    ('I', Segments::from_letters("ahjm")),
+    ('J', Segments::from_letters("cfgh")),
+    ('K', Segments::from_letters("bdfkn")),
+    ('L', Segments::from_letters("bfh")),
+    ('M', Segments::from_letters("bcfgik")),
+    ('N', Segments::from_letters("bcfgin")),
+    ('O', Segments::from_letters("abcfgh")),
+    ('P', Segments::from_letters("abcdef")),
+    ('Q', Segments::from_letters("abcfghn")),
+    ('R', Segments::from_letters("abcdefn")),
+    ('S', Segments::from_letters("aeghi")),
+    ('T', Segments::from_letters("ajm")),
+    ('U', Segments::from_letters("bcfgh")),
+    ('V', Segments::from_letters("bfkl")),
+    ('W', Segments::from_letters("bcfgln")),
+    ('X', Segments::from_letters("ikln")),
+    ('Y', Segments::from_letters("ikm")),
+    ('Z', Segments::from_letters("ahkl")),
+];
+
+/// The symbols a kind of display can show, and the segments lit for each.
+/// Symbols are numbered by their place in the table, which is also their value
+/// as a digit when decoding a number.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Alphabet {
+    segments: usize,
+    symbols: &'static [(char, Segments)],
+}
+
+impl Alphabet {
+    /// The digits 0 to 9 on a seven-segment display, as in the puzzle.
+    pub const DECIMAL: Self = Self::new(7, &DECIMAL_SYMBOLS);
+
+    /// The digits 0 to 9 and A to F on a seven-segment display, with b and d
+    /// in lower case so they don't look like 8 and 0.
+    pub const HEXADECIMAL: Self = Self::new(7, &HEXADECIMAL_SYMBOLS);
+
+    /// The digits 0 to 9 and letters A to Z on a fourteen-segment display.
+    pub const ALPHANUMERIC: Self = Self::new(14, &ALPHANUMERIC_SYMBOLS);
+
+    /// A display with `segments` segments, `a` onwards, showing `symbols`. The
+    /// symbols must all light different segments.
+    pub const fn new(segments: usize, symbols: &'static [(char, Segments)]) -> Self {
+        assert!(segments <= 16, "a display has at most 16 segments");
+        Self { segments, symbols }
+    }
+
+    /// Every segment on the display.
+    pub fn segments(self) -> Segments {
+        Segments::first(self.segments)
+    }
+
+    pub fn symbols(self) -> &'static [(char, Segments)] {
+        self.symbols
+    }
+
+    /// How many symbols there are.
+    pub fn len(self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Whether only one symbol lights `size` segments, so that it can be
+    /// picked out however the display is wired.
+    pub fn has_unique_size(self, size: usize) -> bool {
+        self.symbols
+            .iter()
+            .filter(|(_, segments)| segments.len() == size)
+            .count()
+            == 1
+    }
+
+    /// Parses a line of notes for this display, like
+    /// `be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe`:
+    /// a signal pattern for every symbol, one `|`, then `OUTPUTS` output patterns.
+    pub fn parse_entry(self, input: &str) -> Result<Entry> {
+        let mut parts = input.split('|');
+        let signals = next_token(input, parts.next(), "signal patterns")?;
+
+        let mut input_signals = Vec::<Segments>::new();
+        for signal in signals.split_ascii_whitespace() {
+            if input_signals.len() == self.len() {
+                return Err(ParseError::invalid_token(input, signal, "'|'"));
+            }
+            input_signals.push(self.read_signal_pattern(input, signal)?);
+        }
+
+        let digits = next_token(input, parts.next(), "'|'")?;
+        if input_signals.len() < self.len() {
+            let bar = &input[signals.len()..signals.len() + 1];
+            let expected = format!("{} signal patterns before '|'", self.len());
+            return Err(ParseError::invalid_token(input, bar, &expected));
+        }
+
+        let mut output_digits = Vec::<Segments>::new();
+        for digit_pattern in digits.split_ascii_whitespace() {
+            if output_digits.len() == OUTPUTS {
+                return Err(ParseError::invalid_token(
+                    input,
+                    digit_pattern,
+                    "end of line",
+                ));
+            }
+            output_digits.push(self.read_signal_pattern(input, digit_pattern)?);
+        }
+        if parts.next().is_some() {
+            let end = signals.len() + 1 + digits.len();
+            return Err(ParseError::invalid_token(
+                input,
+                &input[end..end + 1],
+                "end of line",
+            ));
+        }
+        if output_digits.len() < OUTPUTS {
+            let expected = format!("{} output patterns", OUTPUTS);
+            return Err(ParseError::unexpected_end(input, &expected));
+        }
+
+        Ok((input_signals, output_digits))
+    }
+
+    fn read_signal_pattern(self, input: &str, pattern: &str) -> Result<Segments> {
+        let last = self.segments().letters().last().unwrap_or('a');
+        let expected = format!("a signal pattern of the letters a to {}", last);
+
+        match parse_token::<Segments>(input, pattern, &expected)? {
+            wires if wires.difference(self.segments()).is_empty() => Ok(wires),
+            _ => Err(ParseError::invalid_token(input, pattern, &expected)),
+        }
+    }
+
+    /// The one wiring that shows each signal pattern as a different symbol,
+    /// assuming no wire is broken. The patterns need not cover every symbol.
+    pub fn solve_wiring(self, signals: &[Segments]) -> std::result::Result<Wiring, DecodeError> {
+        single(Clues::new(self, signals, &[]).wirings(None))
+    }
+
+    /// Like `solve_wiring`, but if no wiring fits, looks for one that does with
+    /// a single wire stuck off or on. A fault can make two symbols look the
+    /// same, so the signal patterns may repeat.
+    pub fn solve_faulty_wiring(
+        self,
+        signals: &[Segments],
+    ) -> std::result::Result<Wiring, DecodeError> {
+        single(Clues::new(self, signals, &[]).faulty_wirings())
+    }
+
+    /// The output number for a line of notes, using its output patterns as
+    /// clues along with its signal patterns. Tolerates one broken wire, and
+    /// signal patterns that are missing, as long as every wiring that fits
+    /// agrees on the number.
+    pub fn decode_entry(
+        self,
+        (signals, outputs): &Entry,
+    ) -> std::result::Result<usize, DecodeError> {
+        let wirings = Clues::new(self, signals, outputs).faulty_wirings();
+
+        let mut numbers = wirings.iter().map(|wiring| wiring.decode_number(outputs));
+        let number = numbers.next().ok_or(DecodeError::NoWiring)??;
+
+        if numbers.all(|other| other == Ok(number)) {
+            Ok(number)
+        } else {
+            Err(DecodeError::Ambiguous(wirings))
+        }
+    }
+}
+
+/// A wire that is broken, so that its segment never lights or always does.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Fault {
+    StuckOff(char),
+    StuckOn(char),
+}
+
+impl Fault {
+    /// The broken wire.
+    pub fn wire(self) -> char {
+        match self {
+            Fault::StuckOff(wire) | Fault::StuckOn(wire) => wire,
+        }
+    }
+}
+
+/// Which segment each wire of a display drives, and any wire that is broken.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Wiring {
+    alphabet: Alphabet,
+    segments: Vec<char>,
+    fault: Option<Fault>,
+}
+
+impl Wiring {
+    /// A wiring for the puzzle's display, where `segments[i]` is the segment
+    /// driven by the `i`th wire, from `a`, or `None` unless every segment
+    /// `a` to `g` appears once.
+    pub fn new(segments: [char; 7]) -> Option<Self> {
+        Self::for_alphabet(Alphabet::DECIMAL, segments.to_vec())
+    }
+
+    /// A wiring for any display, with a segment for each of its wires, or
+    /// `None` unless each of the display's segments appears once.
+    pub fn for_alphabet(alphabet: Alphabet, segments: Vec<char>) -> Option<Self> {
+        let mut sorted = segments.clone();
+        sorted.sort_unstable();
+        if !sorted.into_iter().eq(alphabet.segments().letters()) {
+            return None;
+        }
+
+        Some(Self {
+            alphabet,
+            segments,
+            fault: None,
+        })
+    }
+
+    pub fn with_fault(self, fault: Fault) -> Self {
+        Self {
+            fault: Some(fault),
+            ..self
+        }
+    }
+
+    pub fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
+
+    /// The segment driven by `wire`, or `None` if the display has no such wire.
+    pub fn segment(&self, wire: char) -> Option<char> {
+        letter_index(wire).and_then(|index| self.segments.get(index).copied())
+    }
+
+    /// The wire that drives `segment`, or `None` if the display has no such segment.
+    pub fn wire(&self, segment: char) -> Option<char> {
+        let index = self.segments.iter().position(|&s| s == segment)?;
+        Some((b'a' + index as u8) as char)
+    }
+
+    pub fn fault(&self) -> Option<Fault> {
+        self.fault
+    }
+
+    /// The wires that light up for the symbol numbered `symbol`.
+    pub fn encode(&self, symbol: usize) -> Segments {
+        self.shown()[symbol]
+    }
+
+    /// The number of the symbol shown by the wires in `pattern`, or `None` if
+    /// it is not a symbol, or a fault makes it look like more than one.
+    pub fn decode(&self, pattern: Segments) -> Option<usize> {
+        let shown = self.shown();
+        let mut symbols = (0..shown.len()).filter(|&symbol| shown[symbol] == pattern);
+
+        match (symbols.next(), symbols.next()) {
+            (Some(symbol), None) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    /// The number shown by the output patterns, most significant first, in
+    /// the base of the number of symbols.
+    pub fn decode_number(&self, digits: &[Segments]) -> std::result::Result<usize, DecodeError> {
+        digits.iter().try_fold(0, |number, &pattern| {
+            let digit = self
+                .decode(pattern)
+                .ok_or(DecodeError::UnknownPattern(pattern))?;
+            Ok(number * self.alphabet.len() + digit)
+        })
+    }
+
+    /// The symbols shown by the output patterns.
+    pub fn decode_text(&self, patterns: &[Segments]) -> std::result::Result<String, DecodeError> {
+        patterns
+            .iter()
+            .map(|&pattern| {
+                self.decode(pattern)
+                    .map(|symbol| self.alphabet.symbols[symbol].0)
+                    .ok_or(DecodeError::UnknownPattern(pattern))
+            })
+            .collect()
+    }
+
+    /// The segments lit by `wires`, ignoring any fault.
+    pub fn unscramble(&self, wires: Segments) -> Segments {
+        wires
+            .letters()
+            .filter_map(|wire| self.segment(wire))
+            .collect()
+    }
+
+    // The wires lit for each symbol
+    fn shown(&self) -> Vec<Segments> {
+        // the wire driving each segment; the constructors check every
+        // segment is one of the display's
+        let mut wires = vec![Segments::NONE; self.segments.len()];
+        for (wire, &segment) in self.segments.iter().enumerate() {
+            if let Some(driver) = letter_index(segment).and_then(|index| wires.get_mut(index)) {
+                *driver = Segments(1 << wire);
+            }
+        }
+
+        self.alphabet
+            .symbols
+            .iter()
+            .map(|(_, segments)| {
+                let lit = segments
+                    .letters()
+                    .filter_map(|segment| letter_index(segment).and_then(|index| wires.get(index)))
+                    .fold(Segments::NONE, |lit, &wire| lit.union(wire));
+                apply_fault(self.fault, lit)
+            })
+            .collect()
+    }
+}
+
+fn apply_fault(fault: Option<Fault>, wires: Segments) -> Segments {
+    match fault {
+        Some(Fault::StuckOff(wire)) => wires.difference(Segments::single(wire).unwrap_or_default()),
+        Some(Fault::StuckOn(wire)) => wires.union(Segments::single(wire).unwrap_or_default()),
+        None => wires,
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DecodeError {
+    /// No wiring shows every pattern as a symbol.
+    NoWiring,
+    /// More than one wiring fits the patterns; holds every one that does.
+    Ambiguous(Vec<Wiring>),
+    /// A pattern that the wiring does not show as one symbol.
+    UnknownPattern(Segments),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::NoWiring => write!(f, "no wiring shows every pattern as a symbol"),
+            DecodeError::Ambiguous(wirings) => {
+                write!(f, "{} wirings fit the patterns", wirings.len())
+            }
+            DecodeError::UnknownPattern(pattern) => {
+                write!(f, "`{}` is not a single symbol", pattern)
+            }
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// `Alphabet::solve_wiring` for the puzzle's display.
+pub fn solve_wiring(signals: &[Segments]) -> std::result::Result<Wiring, DecodeError> {
+    Alphabet::DECIMAL.solve_wiring(signals)
+}
+
+/// `Alphabet::solve_faulty_wiring` for the puzzle's display.
+pub fn solve_faulty_wiring(signals: &[Segments]) -> std::result::Result<Wiring, DecodeError> {
+    Alphabet::DECIMAL.solve_faulty_wiring(signals)
+}
+
+/// `Alphabet::decode_entry` for the puzzle's display.
+pub fn decode_entry(entry: &Entry) -> std::result::Result<usize, DecodeError> {
+    Alphabet::DECIMAL.decode_entry(entry)
+}
+
+fn single(mut wirings: Vec<Wiring>) -> std::result::Result<Wiring, DecodeError> {
+    match wirings.len() {
+        0 => Err(DecodeError::NoWiring),
+        1 => Ok(wirings.remove(0)),
+        _ => Err(DecodeError::Ambiguous(wirings)),
+    }
+}
+
+// What a line of notes says about its wiring: each signal pattern shows a
+// different symbol, and each output pattern shows some symbol
+struct Clues<'a> {
+    alphabet: Alphabet,
+    signals: &'a [Segments],
+    outputs: &'a [Segments],
+}
+
+impl<'a> Clues<'a> {
+    fn new(alphabet: Alphabet, signals: &'a [Segments], outputs: &'a [Segments]) -> Self {
+        Self {
+            alphabet,
+            signals,
+            outputs,
+        }
+    }
+
+    fn patterns(&self) -> impl Iterator<Item = Segments> + '_ {
+        self.signals.iter().chain(self.outputs).copied()
+    }
+
+    // Every wiring with the given fault, if any, that fits the clues
+    fn wirings(&self, fault: Option<Fault>) -> Vec<Wiring> {
+        // a wire stuck off is never lit, and one stuck on always is
+        let possible = match fault {
+            Some(Fault::StuckOff(wire)) => self.patterns().all(|pattern| !pattern.contains(wire)),
+            Some(Fault::StuckOn(wire)) => self.patterns().all(|pattern| pattern.contains(wire)),
+            None => true,
+        };
+        if !possible {
+            return Vec::new();
+        }
+
+        let mut wirings = Vec::new();
+        self.search(&self.domains(fault), fault, &mut Vec::new(), &mut wirings);
+        wirings
+    }
+
+    // The wirings without a fault that fit the clues, or if there are none,
+    // every wiring with one broken wire that does
+    fn faulty_wirings(&self) -> Vec<Wiring> {
+        let wirings = self.wirings(None);
+        if !wirings.is_empty() {
+            return wirings;
+        }
+
+        self.alphabet
+            .segments()
+            .letters()
+            .flat_map(|wire| [Fault::StuckOff(wire), Fault::StuckOn(wire)])
+            .flat_map(|fault| self.wirings(Some(fault)))
+            .collect()
+    }
+
+    // The segments each wire could drive
+    fn domains(&self, fault: Option<Fault>) -> Vec<Segments> {
+        let all = self.alphabet.segments();
+        let broken = fault
+            .and_then(|fault| Segments::single(fault.wire()))
+            .unwrap_or_default();
+        let mut domains = vec![all; self.alphabet.segments];
+
+        // a wire lit in a pattern drives a segment of some symbol with as many
+        // segments, and an unlit wire one that such a symbol leaves off. The
+        // broken wire may or may not really be lit, so it tells us nothing and
+        // the symbol may have one more segment
+        for pattern in self.patterns() {
+            let working = pattern.difference(broken);
+            let sizes = working.len()..=working.len() + broken.len();
+
+            let same_size = self
+                .alphabet
+                .symbols
+                .iter()
+                .map(|&(_, segments)| segments)
+                .filter(|segments| sizes.contains(&segments.len()));
+            let lit = same_size.clone().fold(Segments::NONE, Segments::union);
+            let unlit = same_size.fold(Segments::NONE, |unlit, segments| {
+                unlit.union(all.difference(segments))
+            });
+
+            for (wire, domain) in all.letters().zip(domains.iter_mut()) {
+                if !broken.contains(wire) {
+                    *domain = domain.intersection(if working.contains(wire) { lit } else { unlit });
+                }
+            }
+        }
+
+        // a wire that can only drive one segment rules that segment out for the rest
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for wire in 0..domains.len() {
+                if domains[wire].len() == 1 {
+                    let segment = domains[wire];
+                    for (other, domain) in domains.iter_mut().enumerate() {
+                        if other != wire && !domain.intersection(segment).is_empty() {
+                            *domain = domain.difference(segment);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        domains
+    }
+
+    // Adds every wiring that fits the clues and gives each wire a different
+    // segment from its domain, following on from the segments chosen so far
+    fn search(
+        &self,
+        domains: &[Segments],
+        fault: Option<Fault>,
+        segments: &mut Vec<char>,
+        wirings: &mut Vec<Wiring>,
+    ) {
+        if segments.len() == domains.len() {
+            let wiring = Wiring {
+                alphabet: self.alphabet,
+                segments: segments.clone(),
+                fault,
+            };
+            if self.fit(&wiring.shown()) {
+                wirings.push(wiring);
+            }
+            return;
+        }
+
+        for segment in domains[segments.len()].letters() {
+            if !segments.contains(&segment) {
+                segments.push(segment);
+                if self.could_fit(segments, fault) {
+                    self.search(domains, fault, segments, wirings);
+                }
+                segments.pop();
+            }
+        }
+    }
+
+    // Whether every pattern could still be a symbol, judging by the wires
+    // whose segments have been chosen
+    fn could_fit(&self, segments: &[char], fault: Option<Fault>) -> bool {
+        let broken = fault.map(Fault::wire);
+        let known: Vec<(char, char)> = ('a'..)
+            .zip(segments.iter().copied())
+            .filter(|&(wire, _)| Some(wire) != broken)
+            .collect();
+        let known_segments: Segments = known.iter().map(|&(_, segment)| segment).collect();
+
+        self.patterns().all(|pattern| {
+            let lit: Segments = known
+                .iter()
+                .filter(|&&(wire, _)| pattern.contains(wire))
+                .map(|&(_, segment)| segment)
+                .collect();
+
+            self.alphabet
+                .symbols
+                .iter()
+                .any(|&(_, symbol)| symbol.intersection(known_segments) == lit)
+        })
+    }
+
+    // Whether the clues fit a wiring that lights `shown` for each symbol
+    fn fit(&self, shown: &[Segments]) -> bool {
+        let count = |patterns: &[Segments], pattern: Segments| {
+            patterns.iter().filter(|&&other| other == pattern).count()
+        };
+
+        self.signals
+            .iter()
+            .all(|&signal| count(self.signals, signal) <= count(shown, signal))
+            && self.outputs.iter().all(|output| shown.contains(output))
+    }
+}
+
+/// Works out the wiring from the patterns `determine_input_numbers` found for
+/// each digit, or `None` if they don't fit any wiring.
+pub fn wiring_from_numbers(numbers: &[Segments]) -> Option<Wiring> {
+    let mut segments = ['a'; 7];
+
+    // a segment is driven by the wire that is lit in exactly the same digits
+    let all = Alphabet::DECIMAL.segments();
+    for segment in all.letters() {
+        let wire = all.letters().find(|&wire| {
+            DIGITS
+                .iter()
+                .zip(numbers)
+                .all(|(digit, number)| digit.contains(segment) == number.contains(wire))
+        })?;
+        segments[letter_index(wire)?] = segment;
+    }
+
+    let wiring = Wiring::new(segments)?;
+    (wiring.shown()[..] == numbers[..]).then_some(wiring)
+}
+
+/// Draws each set of segments as a digit on a seven-segment display, side by
+/// side, like
+///
+/// ```text
+///  _  _
+/// |_  _|
+///  _| _|
+/// ```
+pub fn render_segments(patterns: &[Segments]) -> String {
+    // the segment drawn in each cell of a digit, and the mark for it
+    const CELLS: [[Option<(char, char)>; 3]; 3] = [
+        [None, Some(('a', '_')), None],
+        [Some(('b', '|')), Some(('d', '_')), Some(('c', '|'))],
+        [Some(('e', '|')), Some(('g', '_')), Some(('f', '|'))],
+    ];
+
+    CELLS
+        .iter()
+        .map(|cells| {
+            let row = patterns
+                .iter()
+                .map(|&pattern| {
+                    cells
+                        .iter()
+                        .map(|cell| match cell {
+                            Some((segment, mark)) if pattern.contains(*segment) => *mark,
+                            _ => ' ',
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            row.trim_end().to_owned() + "\n"
+        })
+        .collect()
+}
+
+/// Draws the display as the puzzle does, with each segment labelled by the
+/// wire that drives it, or `None` unless it is a seven-segment display like
+/// the puzzle's.
+pub fn render_wiring(wiring: &Wiring) -> Option<String> {
+    if wiring.alphabet().segments() != Alphabet::DECIMAL.segments() {
+        return None;
+    }
+
+    // the wires driving segments a to g
+    let wires: Vec<char> = Alphabet::DECIMAL
+        .segments()
+        .letters()
+        .map(|segment| wiring.wire(segment))
+        .collect::<Option<_>>()?;
+    let [a, b, c, d, e, f, g]: [char; 7] = wires.try_into().ok()?;
+    let across = |wire: char| format!(" {}\n", wire.to_string().repeat(4));
+    let down = |left, right| format!("{}    {}\n", left, right).repeat(2);
+
+    Some(across(a) + &down(b, c) + &across(d) + &down(e, f) + &across(g))
+}
+
+/// What `determine_input_numbers` makes of a line of notes: the wiring it
+/// implies, the output digits as the display would show them, the number they
+/// read, and anything that looks wrong, such as digits missing from the signal
+/// patterns or a disagreement with `decode_entry`.
+pub fn render_entry(entry: &Entry) -> String {
+    let (signals, outputs) = entry;
+    let numbers = determine_input_numbers(signals);
+    let mut rendered = String::new();
+
+    let missing: Vec<String> = numbers
+        .iter()
+        .enumerate()
+        .filter(|(_, number)| number.is_empty())
+        .map(|(digit, _)| digit.to_string())
+        .collect();
+    if !missing.is_empty() {
+        rendered += &format!("no signal pattern for {}\n", missing.join(", "));
+    }
+
+    match wiring_from_numbers(&numbers) {
+        Some(wiring) => {
+            let wires = Alphabet::DECIMAL
+                .segments()
+                .letters()
+                .filter_map(|wire| Some(format!("{}->{}", wire, wiring.segment(wire)?)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            rendered += &format!("wiring: {}\n", wires);
+            rendered += &render_wiring(&wiring).unwrap_or_default();
+
+            let shown: Vec<Segments> = outputs
+                .iter()
+                .map(|&output| wiring.unscramble(output))
+                .collect();
+            rendered += &render_segments(&shown);
+        }
+        None => rendered += "no wiring fits the signal patterns\n",
+    }
+
+    let digits: Vec<Option<usize>> = outputs
+        .iter()
+        .map(|output| numbers.iter().position(|number| number == output))
+        .collect();
+    let reading: String = digits
+        .iter()
+        .map(|digit| digit.map_or('?', |digit| (b'0' + digit as u8) as char))
+        .collect();
+    rendered += &format!("reads {}\n", reading);
+
+    for (output, digit) in outputs.iter().zip(&digits) {
+        if digit.is_none() {
+            rendered += &format!("output `{}` is not one of the signal patterns\n", output);
+        }
+    }
+
+    match decode_entry(entry) {
+        Ok(number) if format!("{:0width$}", number, width = outputs.len()) == reading => (),
+        Ok(number) => rendered += &format!("decode_entry reads {}\n", number),
+        Err(err) => rendered += &format!("decode_entry failed: {}\n", err),
+    }
+
+    rendered
+}
+
+/// `render_entry` for every line of notes, each headed by the line it came from.
+pub fn render_notes(input: &str, entries: &[Entry]) -> String {
+    entries
+        .iter()
+        .zip(input.lines())
+        .enumerate()
+        .map(|(i, (entry, line))| format!("Line {}: {}\n{}\n", i + 1, line, render_entry(entry)))
+        .collect()
+}
+
+/// Writes lines of notes in the form the puzzle gives them.
+pub fn format_notes(entries: &[Entry]) -> String {
+    let patterns = |patterns: &[Segments]| {
+        patterns
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    entries
+        .iter()
+        .map(|(signals, outputs)| format!("{} | {}\n", patterns(signals), patterns(outputs)))
+        .collect()
+}
+
+/// Settings for generating random notes.
+///
+/// Each line wires the display at random, lists every symbol once in a random
+/// order as its signal patterns, then shows `OUTPUTS` random symbols.
+#[derive(Copy, Clone, Debug)]
+pub struct NotesGenerator {
+    pub alphabet: Alphabet,
+    pub lines: usize,
+}
+
+impl Default for NotesGenerator {
+    /// The shape of the real puzzle inputs.
+    fn default() -> Self {
+        Self {
+            alphabet: Alphabet::DECIMAL,
+            lines: 200,
+        }
+    }
+}
+
+/// Generated notes, with how each line is wired and what it should decode to.
+#[derive(Clone, Debug)]
+pub struct Notes {
+    pub alphabet: Alphabet,
+    pub entries: Vec<Entry>,
+    pub wirings: Vec<Wiring>,
+    /// The number shown by each line's outputs, as `Wiring::decode_number` reads it.
+    pub numbers: Vec<usize>,
+}
+
+impl Notes {
+    /// How many outputs light as many segments as only one symbol does, the
+    /// answer to part 1 for the puzzle's display.
+    pub fn unique_outputs(&self) -> usize {
+        self.entries
+            .iter()
+            .flat_map(|(_, outputs)| outputs)
+            .filter(|output| self.alphabet.has_unique_size(output.len()))
+            .count()
+    }
+
+    /// The sum of the output numbers, the answer to part 2 for the puzzle's display.
+    pub fn sum(&self) -> usize {
+        self.numbers.iter().sum()
+    }
+}
+
+impl NotesGenerator {
+    pub fn generate<R: Rng>(&self, rng: &mut R) -> Notes {
+        let mut notes = Notes {
+            alphabet: self.alphabet,
+            entries: Vec::with_capacity(self.lines),
+            wirings: Vec::with_capacity(self.lines),
+            numbers: Vec::with_capacity(self.lines),
+        };
+
+        for _ in 0..self.lines {
+            let mut segments: Vec<char> = self.alphabet.segments().letters().collect();
+            segments.shuffle(rng);
+            let wiring = Wiring {
+                alphabet: self.alphabet,
+                segments,
+                fault: None,
+            };
+
+            let mut symbols: Vec<usize> = (0..self.alphabet.len()).collect();
+            symbols.shuffle(rng);
+            let outputs: Vec<usize> = (0..OUTPUTS)
+                .map(|_| rng.gen_range(0..self.alphabet.len()))
+                .collect();
+
+            let encode = |symbols: &[usize]| -> Vec<Segments> {
+                symbols
+                    .iter()
+                    .map(|&symbol| wiring.encode(symbol))
+                    .collect()
+            };
+            let entry = (encode(&symbols), encode(&outputs));
+
+            notes.numbers.push(
+                outputs
+                    .iter()
+                    .fold(0, |number, symbol| number * self.alphabet.len() + symbol),
+            );
+            notes.entries.push(entry);
+            notes.wirings.push(wiring);
+        }
+
+        notes
+    }
+}
+
+#[cfg(test)]
+mod day8_tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parse_input_signals_and_output_digits() {
+        let input = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe";
+        let (input, output) = parse_input_signals_and_output_digits(input).unwrap();
+
+        assert_eq!(10, input.len());
+
+        assert_eq!(2, input[0].len());
+        assert!(input[0].contains('b'));
+        assert!(input[0].contains('e'));
+
+        assert_eq!(7, input[1].len());
+        assert!(input[1].contains('a'));
+        assert!(input[1].contains('b'));
+        assert!(input[1].contains('c'));
+        assert!(input[1].contains('d'));
+        assert!(input[1].contains('e'));
+        assert!(input[1].contains('f'));
+        assert!(input[1].contains('g'));
+
+        assert_eq!(4, output.len());
+
+        assert_eq!(7, output[0].len());
+        assert!(output[0].contains('a'));
+        assert!(output[0].contains('b'));
+        assert!(output[0].contains('c'));
+        assert!(output[0].contains('d'));
+        assert!(output[0].contains('e'));
+        assert!(output[0].contains('f'));
+        assert!(output[0].contains('g'));
+    }
+
+    #[test]
+    fn test_parse_input_signals_and_output_digits_invalid() {
+        let input = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb";
+        let err = parse_input_signals_and_output_digits(input).unwrap_err();
+        assert_eq!("1:59: expected '|', found end of line", err.to_string());
+
+        let input = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb fdgacbe cefdb";
+        let err = parse_input_signals_and_output_digits(input).unwrap_err();
+        assert_eq!("1:60: expected '|', found `fdgacbe`", err.to_string());
+
+        let input = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd | fdgacbe cefdb";
+        let err = parse_input_signals_and_output_digits(input).unwrap_err();
+        assert_eq!(
+            "1:56: expected 10 signal patterns before '|', found `|`",
+            err.to_string()
+        );
+
+        let err = parse_input_signals_and_output_digits(" | a b c d").unwrap_err();
+        assert_eq!(
+            "1:2: expected 10 signal patterns before '|', found `|`",
+            err.to_string()
+        );
+
+        let signals = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb";
+        let input = format!("{} | fdgacbe cefdb cefbgd", signals);
+        let err = parse_input_signals_and_output_digits(&input).unwrap_err();
+        assert_eq!(
+            "1:82: expected 4 output patterns, found end of line",
+            err.to_string()
+        );
+
+        let input = format!("{} | fdgacbe cefdb cefbgd gcbe be", signals);
+        let err = parse_input_signals_and_output_digits(&input).unwrap_err();
+        assert_eq!("1:88: expected end of line, found `be`", err.to_string());
+
+        let input = format!("{} | fdgacbe Ce`db cefbgd gcbe", signals);
+        let err = parse_input_signals_and_output_digits(&input).unwrap_err();
+        assert_eq!(
+            "1:70: expected a signal pattern of the letters a to g, found `Ce`db`",
+            err.to_string()
+        );
+
+        let input = format!("{} | fdgacbe cefdb | cefbgd gcbe", signals);
+        let err = parse_input_signals_and_output_digits(&input).unwrap_err();
+        assert_eq!("1:76: expected end of line, found `|`", err.to_string());
+
+        let input = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefxb";
+        let err = parse_input_signals_and_output_digits(input).unwrap_err();
+        assert_eq!(
+            "1:70: expected a signal pattern of the letters a to g, found `cefxb`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_segments() {
+        let cf: Segments = "fc".parse().unwrap();
+        let acf: Segments = "cfa".parse().unwrap();
+
+        assert_eq!(Segments(0b010_0100), cf);
+        assert_eq!(Segments::from_letters("cf"), cf);
+        assert_eq!(2, cf.len());
+        assert!(!cf.is_empty());
+        assert!(Segments::NONE.is_empty());
+        assert_eq!(7, Segments::first(7).len());
+
+        assert_eq!(cf, cf.intersection(acf));
+        assert_eq!(acf, cf.union(Segments::single('a').unwrap()));
+        assert_eq!(Segments::single('a'), Some(acf.difference(cf)));
+
+        // nothing outside `a` to `p` is a segment
+        for letter in ['`', 'A', 'q', '0', 'é'] {
+            assert_eq!(None, Segments::single(letter));
+            assert!(!Segments::first(16).contains(letter));
+        }
+        assert_eq!(Segments::single('p'), Some(Segments(1 << 15)));
+        assert_eq!(acf, "a`cfA".chars().collect());
+        assert_eq!(acf, Segments::from_letters("a`cfA"));
+        assert!("`a".parse::<Segments>().is_err());
+        assert_eq!(vec!['a', 'c', 'f'], acf.letters().collect::<Vec<_>>());
+        assert_eq!(acf, "acf".chars().collect());
+
+        assert_eq!("acf", acf.to_string());
+        assert_eq!("abcdefg", Segments::first(7).to_string());
+        assert_eq!("", Segments::NONE.to_string());
+
+        assert!("".parse::<Segments>().is_err());
+        assert!("abq".parse::<Segments>().is_err());
+    }
+
+    #[test]
+    fn test_alphabets() {
+        for alphabet in [
+            Alphabet::DECIMAL,
+            Alphabet::HEXADECIMAL,
+            Alphabet::ALPHANUMERIC,
+        ] {
+            let symbols = alphabet.symbols();
+            for (i, &(symbol, segments)) in symbols.iter().enumerate() {
+                assert!(segments.difference(alphabet.segments()).is_empty());
+                assert!(
+                    symbols[..i].iter().all(|&(_, other)| other != segments),
+                    "{}",
+                    symbol
+                );
+            }
+        }
+
+        let unique: Vec<usize> = (0..=7)
+            .filter(|&size| Alphabet::DECIMAL.has_unique_size(size))
+            .collect();
+        assert_eq!(vec![2, 3, 4, 7], unique);
+        assert_eq!(16, Alphabet::HEXADECIMAL.len());
+        assert_eq!(
+            "abcdefghijklmn",
+            Alphabet::ALPHANUMERIC.segments().to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        let symbols: Vec<String> = Alphabet::ALPHANUMERIC
+            .symbols()
+            .iter()
+            .map(|(_, segments)| segments.to_string())
+            .collect();
+        let line = format!("{} | nm ab cg acegn", symbols.join(" "));
+        let (signals, outputs) = Alphabet::ALPHANUMERIC.parse_entry(&line).unwrap();
+        assert_eq!(Alphabet::ALPHANUMERIC.len(), signals.len());
+        assert_eq!(Alphabet::ALPHANUMERIC.symbols()[35].1, signals[35]);
+        assert_eq!(patterns("mn ab cg acegn"), outputs);
+
+        let err = Alphabet::HEXADECIMAL.parse_entry(&line).unwrap_err();
+        assert_eq!(
+            "1:1: expected a signal pattern of the letters a to g, found `abcfghkl`",
+            err.to_string()
+        );
+
+        let err = parse_input_signals_and_output_digits("cg acegn | nm").unwrap_err();
+        assert_eq!(
+            "1:4: expected a signal pattern of the letters a to g, found `acegn`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_sample_part_1() {
+        let input = fs::read_to_string("./data/sample8.txt").unwrap();
+
+        let mut count = 0;
+        for line in input.lines() {
+            let (_, output) = parse_input_signals_and_output_digits(line).unwrap();
+            for digit in output {
+                // 1 has 2 segments
+                // 4 has 4 segments
+                // 7 has 3 segments
+                // 8 has 7 segments
+                if [2, 4, 3, 7].contains(&digit.len()) {
+                    count += 1;
+                }
+            }
+        }
+
+        assert_eq!(26, count);
+    }
+
+    #[test]
+    fn test_determine_input_numbers() {
+        let input =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+        let (input, _) = parse_input_signals_and_output_digits(input).unwrap();
+
+        let input_numbers = determine_input_numbers(&input);
+
+        assert_eq!("abcdeg", input_numbers[0].to_string());
+        assert_eq!("ab", input_numbers[1].to_string());
+        assert_eq!("acdfg", input_numbers[2].to_string());
+        assert_eq!("abcdf", input_numbers[3].to_string());
+        assert_eq!("abef", input_numbers[4].to_string());
+        assert_eq!("bcdef", input_numbers[5].to_string());
+        assert_eq!("bcdefg", input_numbers[6].to_string());
+        assert_eq!("abd", input_numbers[7].to_string());
+        assert_eq!("abcdefg", input_numbers[8].to_string());
+        assert_eq!("abcdef", input_numbers[9].to_string());
+    }
+
+    #[test]
+    fn test_determine_output_number() {
+        let input =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+        let (input, output) = parse_input_signals_and_output_digits(input).unwrap();
+
+        let input_numbers = determine_input_numbers(&input);
+        let output_number = determine_output_number(&input_numbers, &output);
+
+        assert_eq!(Ok(5353), output_number);
+    }
+
+    #[test]
+    fn test_sample_part_2() {
+        let input = fs::read_to_string("./data/sample8.txt").unwrap();
+
+        let mut sum: usize = 0;
+        for line in input.lines() {
+            let (input_digits, output_digits) =
+                parse_input_signals_and_output_digits(line).unwrap();
+            let input = determine_input_numbers(&input_digits);
+            let output_number = determine_output_number(&input, &output_digits).unwrap();
+
+            sum += output_number;
+        }
+
+        assert_eq!(61229, sum);
+    }
+
+    fn patterns(patterns: &str) -> Vec<Segments> {
+        patterns
+            .split_ascii_whitespace()
+            .map(|pattern| pattern.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_part_2_missing_pattern() {
+        // the pattern for 5, `cdfbe`, is missing, and 5 is in the output
+        let entries = vec![(
+            patterns("acedgfb gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab"),
+            patterns("cdfeb fcadb cdfeb cdbaf"),
+        )];
+
+        let part2 = Day8::part2(&entries);
+        assert_eq!(
+            Fallible(Err(DecodeError::UnknownPattern("bcdef".parse().unwrap()))),
+            part2
+        );
+        assert_eq!("error: `bcdef` is not a single symbol", part2.to_string());
+
+        // a 3 is still fine without the 5
+        let entries = vec![(entries[0].0.clone(), patterns("fcadb cdbaf"))];
+        assert_eq!(Fallible(Ok(33)), Day8::part2(&entries));
+    }
+
+    #[test]
+    fn test_solve_wiring() {
+        let signals = patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab");
+        let wiring = solve_wiring(&signals).unwrap();
+
+        assert_eq!(
+            Wiring::new(['c', 'f', 'g', 'a', 'b', 'd', 'e']).unwrap(),
+            wiring
+        );
+        assert_eq!(Some('a'), wiring.segment('d'));
+        assert_eq!(None, wiring.segment('h'));
+        assert_eq!(None, wiring.segment('A'));
+        assert_eq!(None, Wiring::new(['c', 'f', 'g', 'a', 'b', 'd', 'A']));
+        assert_eq!(None, Wiring::new(['c', 'f', 'g', 'a', 'b', 'd', 'd']));
+        assert_eq!(
+            None,
+            Wiring::for_alphabet(Alphabet::HEXADECIMAL, vec!['a', 'b'])
+        );
+        assert_eq!(Some('d'), wiring.wire('a'));
+        assert_eq!(None, wiring.wire('h'));
+        assert_eq!(None, wiring.fault());
+        assert_eq!(Some(5), wiring.decode(patterns("cdfbe")[0]));
+        assert_eq!(None, wiring.decode(patterns("cdfb")[0]));
+        assert_eq!(
+            Ok(5353),
+            wiring.decode_number(&patterns("cdfeb fcadb cdfeb cdbaf"))
+        );
+        for (digit, signal) in [8, 5, 2, 3, 7, 9, 6, 4, 0, 1].into_iter().zip(&signals) {
+            assert_eq!(*signal, wiring.encode(digit));
+        }
+    }
+
+    #[test]
+    fn test_solve_wiring_incomplete() {
+        // each pair of the segments 1, 4 and 8 add could be either way round
+        let signals = patterns("acedgfb dab eafb ab");
+        match solve_wiring(&signals) {
+            Err(DecodeError::Ambiguous(wirings)) => assert_eq!(8, wirings.len()),
+            other => panic!("{:?}", other),
+        }
+
+        // but some outputs can be read anyway
+        let entry = (patterns("ab dab"), patterns("ab dab eafb ba"));
+        assert_eq!(Ok(1741), decode_entry(&entry));
+
+        let entry = (patterns("ab dab"), patterns("ab cdfbe"));
+        assert!(matches!(
+            decode_entry(&entry),
+            Err(DecodeError::Ambiguous(_))
+        ));
+
+        assert_eq!(
+            Err(DecodeError::NoWiring),
+            solve_wiring(&patterns("ab abc abcd abcde"))
+        );
+        assert_eq!(
+            Err(DecodeError::UnknownPattern(Segments::from_letters("abcd"))),
+            Wiring::new(['a', 'b', 'c', 'd', 'e', 'f', 'g'])
+                .unwrap()
+                .decode_number(&patterns("abcd"))
+        );
+    }
+
+    #[test]
+    fn test_solve_faulty_wiring() {
+        let wiring = Wiring::new(['c', 'f', 'g', 'a', 'b', 'd', 'e']).unwrap();
+
+        for fault in [Fault::StuckOff('f'), Fault::StuckOn('a')] {
+            let faulty = wiring.clone().with_fault(fault);
+            let signals: Vec<Segments> = (0..10).map(|digit| faulty.encode(digit)).collect();
+
+            assert_eq!(Err(DecodeError::NoWiring), solve_wiring(&signals));
+            assert_eq!(Ok(faulty.clone()), solve_faulty_wiring(&signals));
+
+            let outputs: Vec<Segments> = [4, 3, 7, 1].map(|digit| faulty.encode(digit)).to_vec();
+            assert_eq!(Ok(4371), decode_entry(&(signals, outputs)));
+        }
+
+        // with the middle segment stuck off, 0 and 8 look the same
+        let faulty = wiring.with_fault(Fault::StuckOff('f'));
+        assert_eq!(None, faulty.decode(faulty.encode(8)));
+    }
+
+    #[test]
+    fn test_decode_entry_sample() {
+        let input = fs::read_to_string("./data/sample8.txt").unwrap();
+        let entries = Day8::parse(&input).unwrap();
+
+        let sum: usize = entries
+            .iter()
+            .map(|entry| decode_entry(entry).unwrap())
+            .sum();
+        assert_eq!(61229, sum);
+    }
+
+    #[test]
+    fn test_solve_random_wirings() {
+        use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(21);
+
+        for _ in 0..20 {
+            let mut segments: Vec<char> = ('a'..='g').collect();
+            segments.shuffle(&mut rng);
+            let mut wiring = Wiring::new(segments.try_into().unwrap()).unwrap();
+
+            let mut digits: Vec<usize> = (0..10).collect();
+            digits.shuffle(&mut rng);
+            let signals: Vec<Segments> = digits.iter().map(|&digit| wiring.encode(digit)).collect();
+            assert_eq!(Ok(wiring.clone()), solve_wiring(&signals));
+
+            let wire = rng.gen_range('a'..='g');
+            let fault = if rng.gen() {
+                Fault::StuckOff(wire)
+            } else {
+                Fault::StuckOn(wire)
+            };
+            wiring = wiring.with_fault(fault);
+            let signals: Vec<Segments> = digits.iter().map(|&digit| wiring.encode(digit)).collect();
+            assert_eq!(Ok(wiring), solve_faulty_wiring(&signals), "{:?}", fault);
+        }
+    }
+
+    #[test]
+    fn test_wiring_from_numbers() {
+        let signals = patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab");
+        let numbers = determine_input_numbers(&signals);
+
+        let wiring = wiring_from_numbers(&numbers).unwrap();
+        assert_eq!(solve_wiring(&signals), Ok(wiring.clone()));
+        assert_eq!(DIGITS[5], wiring.unscramble(numbers[5]));
+
+        let mut numbers = numbers;
+        numbers[2] = Segments::NONE;
+        assert_eq!(None, wiring_from_numbers(&numbers));
+    }
+
+    #[test]
+    fn test_render_segments() {
+        assert_eq!(
+            " _       _   _       _   _   _   _   _\n\
+             | |   |  _|  _| |_| |_  |_    | |_| |_|\n\
+             |_|   | |_   _|   |  _| |_|   | |_|  _|\n",
+            render_segments(&DIGITS)
+        );
+        assert_eq!("\n\n\n", render_segments(&[]));
+    }
+
+    #[test]
+    fn test_render_wiring() {
+        let wiring = solve_wiring(&patterns(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab",
+        ))
+        .unwrap();
+
+        // the diagram from the puzzle
+        assert_eq!(
+            " dddd\n\
+             e    a\n\
+             e    a\n \
+              ffff\n\
+             g    b\n\
+             g    b\n \
+              cccc\n",
+            render_wiring(&wiring).unwrap()
+        );
+
+        let segments = Alphabet::ALPHANUMERIC.segments().letters().collect();
+        let wiring = Wiring::for_alphabet(Alphabet::ALPHANUMERIC, segments).unwrap();
+        assert_eq!(None, render_wiring(&wiring));
+    }
+
+    #[test]
+    fn test_render_entry() {
+        let entry = parse_input_signals_and_output_digits(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        )
+        .unwrap();
+
+        let rendered = render_entry(&entry);
+        assert!(rendered.starts_with("wiring: a->c b->f c->g d->a e->b f->d g->e\n dddd\n"));
+        assert!(rendered.ends_with(
+            " _   _   _   _\n\
+             |_   _| |_   _|\n \
+              _|  _|  _|  _|\n\
+             reads 5353\n"
+        ));
+
+        // without the pattern for 2, the decoder can't read it, but the solver can
+        let entry = (
+            patterns("acedgfb cdfbe fbcad dab cefabd cdfgeb eafb cagedb ab"),
+            patterns("gcdfa fcadb cdfeb cdbaf"),
+        );
+
+        let rendered = render_entry(&entry);
+        assert!(rendered.contains("no signal pattern for 2\n"));
+        assert!(rendered.contains("no wiring fits the signal patterns\n"));
+        assert!(rendered.contains("reads ?353\n"));
+        assert!(rendered.contains("output `acdfg` is not one of the signal patterns\n"));
+        assert!(rendered.ends_with("decode_entry reads 2353\n"));
+    }
+
+    #[test]
+    fn test_decode_alphabets() {
+        use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(24);
+
+        for alphabet in [
+            Alphabet::DECIMAL,
+            Alphabet::HEXADECIMAL,
+            Alphabet::ALPHANUMERIC,
+        ] {
+            for _ in 0..5 {
+                let mut segments: Vec<char> = alphabet.segments().letters().collect();
+                segments.shuffle(&mut rng);
+                let wiring = Wiring::for_alphabet(alphabet, segments).unwrap();
+
+                let mut symbols: Vec<usize> = (0..alphabet.len()).collect();
+                symbols.shuffle(&mut rng);
+                let signals: Vec<Segments> = symbols
+                    .iter()
+                    .map(|&symbol| wiring.encode(symbol))
+                    .collect();
+                assert_eq!(Ok(wiring.clone()), alphabet.solve_wiring(&signals));
+
+                let text: Vec<usize> = (0..4).map(|_| rng.gen_range(0..alphabet.len())).collect();
+                let outputs: Vec<Segments> =
+                    text.iter().map(|&symbol| wiring.encode(symbol)).collect();
+                let expected: String = text
+                    .iter()
+                    .map(|&symbol| alphabet.symbols()[symbol].0)
+                    .collect();
+                let number = text
+                    .iter()
+                    .fold(0, |number, symbol| number * alphabet.len() + symbol);
+
+                assert_eq!(Ok(expected), wiring.decode_text(&outputs));
+                assert_eq!(Ok(number), alphabet.decode_entry(&(signals, outputs)));
+
+                let wire = alphabet
+                    .segments()
+                    .letters()
+                    .nth(rng.gen_range(0..alphabet.segments().len()))
+                    .unwrap();
+                let faulty = wiring.with_fault(if rng.gen() {
+                    Fault::StuckOff(wire)
+                } else {
+                    Fault::StuckOn(wire)
+                });
+                let signals: Vec<Segments> = symbols
+                    .iter()
+                    .map(|&symbol| faulty.encode(symbol))
+                    .collect();
+                assert_eq!(Ok(faulty.clone()), alphabet.solve_faulty_wiring(&signals));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_hexadecimal() {
+        let alphabet = Alphabet::HEXADECIMAL;
+        let wiring =
+            Wiring::for_alphabet(alphabet, vec!['c', 'f', 'g', 'a', 'b', 'd', 'e']).unwrap();
+
+        let outputs: Vec<Segments> = [0xb, 0xe, 0xe, 0xf]
+            .map(|symbol| wiring.encode(symbol))
+            .to_vec();
+        assert_eq!(Ok("bEEF".to_owned()), wiring.decode_text(&outputs));
+        assert_eq!(Ok(0xbeef), wiring.decode_number(&outputs));
+    }
+
+    #[test]
+    fn test_generate_notes() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let notes = NotesGenerator::default().generate(&mut StdRng::seed_from_u64(25));
+        assert_eq!(200, notes.entries.len());
+
+        // the text goes through the puzzle's parser and solver unchanged
+        let input = format_notes(&notes.entries);
+        assert_eq!(200, input.lines().count());
+        let entries = Day8::parse(&input).unwrap();
+        assert_eq!(notes.entries, entries);
+        assert_eq!(Fallible(Ok(notes.unique_outputs())), Day8::part1(&entries));
+        assert_eq!(Fallible(Ok(notes.sum())), Day8::part2(&entries));
+
+        for ((entry, wiring), &number) in entries.iter().zip(&notes.wirings).zip(&notes.numbers) {
+            let (signals, outputs) = entry;
+            assert_eq!(
+                Some(wiring.clone()),
+                wiring_from_numbers(&determine_input_numbers(signals))
+            );
+            assert_eq!(
+                Ok(number),
+                determine_output_number(&determine_input_numbers(signals), outputs)
+            );
+            assert_eq!(Ok(number), decode_entry(entry));
+        }
+
+        let again = NotesGenerator::default().generate(&mut StdRng::seed_from_u64(25));
+        assert_eq!(input, format_notes(&again.entries));
+    }
+
+    #[test]
+    fn test_generate_notes_alphabets() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(25);
+
+        for alphabet in [Alphabet::HEXADECIMAL, Alphabet::ALPHANUMERIC] {
+            let generator = NotesGenerator {
+                alphabet,
+                lines: 10,
+            };
+            let notes = generator.generate(&mut rng);
+            let input = format_notes(&notes.entries);
+
+            for (line, &number) in input.lines().zip(&notes.numbers) {
+                let entry = alphabet.parse_entry(line).unwrap();
+                assert_eq!(OUTPUTS, entry.1.len());
+                assert_eq!(Ok(number), alphabet.decode_entry(&entry));
+            }
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::{HashSet, VecDeque};
+
+use itertools::Itertools;
+
+use crate::{parse, Day, Grid, Result};
+
+pub struct Day9;
+
+impl Day for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Grid<u8>;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        build_height_map(input)
+    }
+
+    fn part1(height_map: &Self::Input) -> Self::Output {
+        let low_points = find_low_locations(height_map);
+        sum_risk_levels_of_low_points(&low_points)
+    }
+
+    fn part2(height_map: &Self::Input) -> Self::Output {
+        multiply_largest_basin_sizes(&find_basin_sizes(height_map))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Eq, Hash)]
+pub struct Location {
+    pub row: usize,
+    pub col: usize,
+    pub height: u8,
+}
+
+impl Location {
+    pub fn new(row: usize, col: usize, height: u8) -> Self {
+        Self { row, col, height }
+    }
+}
+
+pub fn build_height_map(input: &str) -> Result<Grid<u8>> {
+    parse::digit_grid(input)
+}
+
+pub fn read_height_map_line(line: &str) -> Result<Vec<u8>> {
+    // parse "2199943210"
+    parse::digit_line(line)
+}
+
+pub fn get_adjacent_locations(height_map: &Grid<u8>, location: Location) -> Vec<Location> {
+    // above, below, left, right
+    height_map
+        .neighbors4(location.row, location.col)
+        .map(|(row, col)| Location::new(row, col, height_map[(row, col)]))
+        .collect()
+}
+
+pub fn is_low_point(height_map: &Grid<u8>, row: usize, col: usize) -> bool {
+    let height = height_map[(row, col)];
+
+    // 9's can't be a low point
+    if height == 9 {
+        return false;
+    }
+
+    // 0's are low points
+    if height == 0 {
+        return true;
+    }
+
+    let adjacent_locations = get_adjacent_locations(height_map, Location::new(row, col, height));
+    height
+        < adjacent_locations
+            .iter()
+            .map(|loc| loc.height)
+            .min()
+            .unwrap()
+}
+
+pub fn find_low_locations(height_map: &Grid<u8>) -> Vec<Location> {
+    height_map
+        .positions()
+        .filter(|&(row, col)| is_low_point(height_map, row, col))
+        .map(|(row, col)| Location::new(row, col, height_map[(row, col)]))
+        .collect()
+}
+
+pub fn sum_risk_levels_of_low_points(low_points: &[Location]) -> usize {
+    low_points
+        .iter()
+        .map(|low_point| low_point.height as usize + 1)
+        .sum()
+}
+
+pub fn find_basin_sizes(height_map: &Grid<u8>) -> Vec<usize> {
+    let low_points = find_low_locations(height_map);
+    let mut basin_sizes = Vec::new();
+
+    for low_point in low_points {
+        let mut basin = HashSet::new();
+        let mut to_visit = VecDeque::new();
+        to_visit.push_back(low_point);
+
+        while !to_visit.is_empty() {
+            let current_location = to_visit.pop_front().unwrap();
+            if !basin.contains(&current_location) {
+                basin.insert(current_location);
+
+                let neighbors = get_adjacent_locations(height_map, current_location);
+                for neighbor in neighbors {
+                    if neighbor.height < 9 && !basin.contains(&neighbor) {
+                        to_visit.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        basin_sizes.push(basin.len());
+    }
+
+    basin_sizes
+}
+
+pub fn multiply_largest_basin_sizes(basin_sizes: &[usize]) -> usize {
+    basin_sizes.iter().sorted().rev().take(3).product()
+}
+
+#[cfg(test)]
+mod day9_tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_read_height_map_line() {
+        let line = "2199943210";
+        let height_map = read_height_map_line(line).unwrap();
+
+        assert_eq!(vec![2, 1, 9, 9, 9, 4, 3, 2, 1, 0], height_map);
+    }
+
+    #[test]
+    fn test_build_height_map_invalid() {
+        let err = build_height_map("2199943210\n398789492a").unwrap_err();
+        assert_eq!("2:10: expected a digit, found `a`", err.to_string());
+    }
+
+    #[test]
+    fn test_build_height_map_from_sample() {
+        let input = fs::read_to_string("./data/sample9.txt").unwrap();
+
+        let height_map = build_height_map(&input).unwrap();
+
+        assert_eq!(vec![2, 1, 9, 9, 9, 4, 3, 2, 1, 0], height_map.row(0));
+        assert_eq!(vec![3, 9, 8, 7, 8, 9, 4, 9, 2, 1], height_map.row(1));
+        assert_eq!(vec![9, 8, 5, 6, 7, 8, 9, 8, 9, 2], height_map.row(2));
+        assert_eq!(vec![8, 7, 6, 7, 8, 9, 6, 7, 8, 9], height_map.row(3));
+        assert_eq!(vec![9, 8, 9, 9, 9, 6, 5, 6, 7, 8], height_map.row(4));
+    }
+
+    #[test]
+    fn test_get_adjacent_locations() {
+        let input = fs::read_to_string("./data/sample9.txt").unwrap();
+
+        /*
+        2199943210
+        3987894921
+        9856789892
+        8767896789
+        9899965678
+        */
+        let height_map = build_height_map(&input).unwrap();
+
+        assert_eq!(
+            vec![Location::new(1, 0, 3), Location::new(0, 1, 1)],
+            get_adjacent_locations(&height_map, Location::new(0, 0, 2))
+        );
+
+        assert_eq!(
+            vec![
+                Location::new(1, 5, 9),
+                Location::new(0, 4, 9),
+                Location::new(0, 6, 3)
+            ],
+            get_adjacent_locations(&height_map, Location::new(0, 5, 4))
+        );
+
+        assert_eq!(
+            vec![Location::new(1, 9, 1), Location::new(0, 8, 1)],
+            get_adjacent_locations(&height_map, Location::new(0, 9, 0))
+        );
+
+        assert_eq!(
+            vec![
+                Location::new(1, 2, 8),
+                Location::new(3, 2, 6),
+                Location::new(2, 1, 8),
+                Location::new(2, 3, 6)
+            ],
+            get_adjacent_locations(&height_map, Location::new(2, 2, 5))
+        );
+    }
+
+    #[test]
+    fn test_is_low_point() {
+        let input = fs::read_to_string("./data/sample9.txt").unwrap();
+
+        /*
+        2199943210
+        3987894921
+        9856789892
+        8767896789
+        9899965678
+        */
+        let height_map = build_height_map(&input).unwrap();
+
+        assert!(!is_low_point(&height_map, 0, 0));
+        assert!(is_low_point(&height_map, 0, 1));
+        assert!(is_low_point(&height_map, 0, 9));
+
+        assert!(!is_low_point(&height_map, 2, 1));
+        assert!(is_low_point(&height_map, 2, 2));
+        assert!(!is_low_point(&height_map, 2, 3));
+
+        assert!(is_low_point(&height_map, 4, 6));
+    }
+
+    #[test]
+    fn test_find_low_points() {
+        let input = fs::read_to_string("./data/sample9.txt").unwrap();
+
+        /*
+        2199943210
+        3987894921
+        9856789892
+        8767896789
+        9899965678
+        */
+        let height_map = build_height_map(&input).unwrap();
+        let low_points = find_low_locations(&height_map);
+        assert_eq!(
+            vec![
+                Location::new(0, 1, 1),
+                Location::new(0, 9, 0),
+                Location::new(2, 2, 5),
+                Location::new(4, 6, 5)
+            ],
+            low_points
+        );
+
+        let sum = sum_risk_levels_of_low_points(&low_points);
+        assert_eq!(15, sum);
+    }
+
+    #[test]
+    fn test_find_low_locations() {
+        let input = fs::read_to_string("./data/sample9.txt").unwrap();
+
+        /*
+        2199943210
+        3987894921
+        9856789892
+        8767896789
+        9899965678
+        */
+        let height_map = build_height_map(&input).unwrap();
+        let low_points = find_low_locations(&height_map);
+
+        assert_eq!(Location::new(0, 1, 1), low_points[0]);
+        assert_eq!(Location::new(0, 9, 0), low_points[1]);
+        assert_eq!(Location::new(2, 2, 5), low_points[2]);
+        assert_eq!(Location::new(4, 6, 5), low_points[3]);
+    }
+
+    #[test]
+    fn test_find_basin_sizes() {
+        let input = fs::read_to_string("./data/sample9.txt").unwrap();
+
+        let height_map = build_height_map(&input).unwrap();
+        let basin_sizes = find_basin_sizes(&height_map);
+        assert_eq!(vec![3, 9, 14, 9], basin_sizes);
+
+        let mul = basin_sizes
+            .iter()
+            .sorted()
+            .rev()
+            .take(3)
+            .map(|x| *x as i32)
+            .product();
+        assert_eq!(1134, mul);
+    }
+}
@@ -0,0 +1,224 @@
+use std::{error::Error, fmt, path::PathBuf, str::FromStr};
+
+/// Where in a puzzle input a parse error occurred. Lines and columns start at 1.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Location {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseError {
+    /// A token was found that is not what the parser expected at that point.
+    InvalidToken {
+        location: Location,
+        expected: String,
+        found: String,
+    },
+    /// The input ran out while the parser still expected something.
+    UnexpectedEnd {
+        location: Location,
+        expected: String,
+    },
+}
+
+impl ParseError {
+    /// An error for `token`, which must be a slice of `source`. The line and column are
+    /// worked out from where `token` sits inside `source`.
+    pub fn invalid_token(source: &str, token: &str, expected: &str) -> Self {
+        let (line, column) = line_and_column(source, offset_of(source, token));
+
+        ParseError::InvalidToken {
+            location: Location {
+                file: None,
+                line,
+                column,
+                len: token.chars().count().max(1),
+            },
+            expected: expected.to_owned(),
+            found: token.to_owned(),
+        }
+    }
+
+    /// An error pointing just past the end of `source`.
+    pub fn unexpected_end(source: &str, expected: &str) -> Self {
+        let (line, column) = line_and_column(source, source.len());
+
+        ParseError::UnexpectedEnd {
+            location: Location {
+                file: None,
+                line,
+                column,
+                len: 1,
+            },
+            expected: expected.to_owned(),
+        }
+    }
+
+    pub fn location(&self) -> &Location {
+        match self {
+            ParseError::InvalidToken { location, .. } => location,
+            ParseError::UnexpectedEnd { location, .. } => location,
+        }
+    }
+
+    fn location_mut(&mut self) -> &mut Location {
+        match self {
+            ParseError::InvalidToken { location, .. } => location,
+            ParseError::UnexpectedEnd { location, .. } => location,
+        }
+    }
+
+    /// Records which file the input that failed to parse came from.
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.location_mut().file = Some(file.into());
+        self
+    }
+
+    /// Moves the error down by `lines`, for when a parser only saw part of the input.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.location_mut().line += lines;
+        self
+    }
+
+    fn message(&self) -> String {
+        match self {
+            ParseError::InvalidToken {
+                expected, found, ..
+            } => format!("expected {}, found `{}`", expected, found),
+            ParseError::UnexpectedEnd { expected, .. } => {
+                format!("expected {}, found end of line", expected)
+            }
+        }
+    }
+
+    /// Renders the error as a diagnostic that quotes the offending line of `source`
+    /// and underlines the span that failed to parse.
+    pub fn render(&self, source: &str) -> String {
+        let location = self.location();
+        let file = match &location.file {
+            Some(file) => file.display().to_string(),
+            None => "<input>".to_owned(),
+        };
+
+        let mut diagnostic = format!(
+            "error: {}\n --> {}:{}:{}\n",
+            self.message(),
+            file,
+            location.line,
+            location.column
+        );
+
+        if let Some(text) = source.lines().nth(location.line - 1) {
+            let line_number = location.line.to_string();
+            let gutter = " ".repeat(line_number.len());
+
+            diagnostic += &format!("{} |\n", gutter);
+            diagnostic += &format!("{} | {}\n", line_number, text);
+            diagnostic += &format!(
+                "{} | {}{}\n",
+                gutter,
+                " ".repeat(location.column - 1),
+                "^".repeat(location.len)
+            );
+        }
+
+        diagnostic
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location = self.location();
+        if let Some(file) = &location.file {
+            write!(f, "{}:", file.display())?;
+        }
+
+        write!(
+            f,
+            "{}:{}: {}",
+            location.line,
+            location.column,
+            self.message()
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token`, a slice of `source`, reporting `expected` if it is not valid.
+pub fn parse_token<T: FromStr>(source: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::invalid_token(source, token, expected))
+}
+
+/// Unwraps the next token from a split of `source`, reporting `expected` if there is none.
+pub fn next_token<'a>(
+    source: &str,
+    token: Option<&'a str>,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    token.ok_or_else(|| ParseError::unexpected_end(source, expected))
+}
+
+fn offset_of(source: &str, token: &str) -> usize {
+    let start = source.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize).wrapping_sub(start);
+
+    if offset <= source.len() {
+        offset
+    } else {
+        0
+    }
+}
+
+fn line_and_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+    (line, before[line_start..].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_token_location() {
+        let source = "16,1,2\n0,x,2";
+        let token = &source[9..10];
+        let err = ParseError::invalid_token(source, token, "a number");
+
+        assert_eq!(2, err.location().line);
+        assert_eq!(3, err.location().column);
+        assert_eq!("2:3: expected a number, found `x`", err.to_string());
+    }
+
+    #[test]
+    fn test_unexpected_end_location() {
+        let err = ParseError::unexpected_end("0,9 -> 5", "','").offset_lines(3);
+
+        assert_eq!(4, err.location().line);
+        assert_eq!(9, err.location().column);
+    }
+
+    #[test]
+    fn test_render() {
+        let source = "0,9 -> 5,9\n8,0 -> 0,y";
+        let token = &source[20..21];
+        let err = ParseError::invalid_token(source, token, "a coordinate").with_file("day5.txt");
+
+        assert_eq!(
+            "error: expected a coordinate, found `y`\n \
+             --> day5.txt:2:10\n  \
+               |\n\
+             2 | 8,0 -> 0,y\n  \
+               |          ^\n",
+            err.render(source)
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid stored row-major in one contiguous `Vec`, indexed by `(row, col)`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len());
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.index_of(row, col).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.index_of(row, col)
+            .map(move |index| &mut self.cells[index])
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Every cell in row-major order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every `(row, col)` position in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// The positions directly above, below, left and right of `(row, col)` that are
+    /// inside the grid, in that order.
+    pub fn neighbors4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offset_positions(row, col, &OFFSETS_4)
+    }
+
+    /// The positions around `(row, col)`, including diagonals, that are inside the grid,
+    /// in row-major order.
+    pub fn neighbors8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offset_positions(row, col, &OFFSETS_8)
+    }
+
+    fn offset_positions(
+        &self,
+        row: usize,
+        col: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+
+        offsets.iter().filter_map(move |(row_offset, col_offset)| {
+            let row = row.checked_add_signed(*row_offset)?;
+            let col = col.checked_add_signed(*col_offset)?;
+            (row < height && col < width).then_some((row, col))
+        })
+    }
+
+    fn index_of(&self, row: usize, col: usize) -> Option<usize> {
+        (row < self.height && col < self.width).then(|| row * self.width + col)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(col < self.width, "column {} out of bounds", col);
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(col < self.width, "column {} out of bounds", col);
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+/// Prints each row on its own line with the cells run together, which suits grids of
+/// single digits or characters.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use super::*;
+
+    fn sample() -> Grid<u8> {
+        // 123
+        // 456
+        Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6])
+    }
+
+    #[test]
+    fn test_index() {
+        let mut grid = sample();
+
+        assert_eq!(2, grid[(0, 1)]);
+        assert_eq!(4, grid[(1, 0)]);
+        assert_eq!(Some(&6), grid.get(1, 2));
+        assert_eq!(None, grid.get(2, 0));
+        assert_eq!(None, grid.get(0, 3));
+
+        grid[(1, 1)] = 9;
+        assert_eq!(9, grid[(1, 1)]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = sample();
+
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(
+            vec![&[1, 2, 3][..], &[4, 5, 6]],
+            grid.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            grid.columns()
+                .map(|col| col.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_positions() {
+        let grid = sample();
+
+        assert_eq!(
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)],
+            grid.positions().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_neighbors4() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbors4(0, 0).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 1), (2, 1), (1, 0), (1, 2)],
+            grid.neighbors4(1, 1).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 2), (2, 1)],
+            grid.neighbors4(2, 2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_neighbors8() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(
+            vec![(0, 1), (1, 0), (1, 1)],
+            grid.neighbors8(0, 0).collect::<Vec<_>>()
+        );
+        assert_eq!(8, grid.neighbors8(1, 1).count());
+        assert_eq!(
+            vec![(1, 1), (1, 2), (2, 1)],
+            grid.neighbors8(2, 2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("123\n456", sample().to_string());
+    }
+}
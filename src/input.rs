@@ -0,0 +1,158 @@
+//! Where a day's puzzle input is read from.
+
+use std::{
+    env,
+    ffi::OsString,
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// The environment variable naming a directory that holds `dayN.txt` inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const DEFAULT_INPUT_DIR: &str = "./data";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Picks where to read a day's input from. An explicit `path` wins, with `-` meaning
+    /// stdin. Otherwise the input is `dayN.txt` in `$AOC_INPUT_DIR`, or in `./data` when
+    /// that is not set.
+    pub fn resolve(day: u8, path: Option<&str>) -> Self {
+        Self::resolve_with(day, path, env::var_os(INPUT_DIR_VAR))
+    }
+
+    fn resolve_with(day: u8, path: Option<&str>, input_dir: Option<OsString>) -> Self {
+        match path {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => InputSource::File(input_dir_from(input_dir).join(format!("day{}.txt", day))),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// The name to show for this source in diagnostics.
+    pub fn name(&self) -> PathBuf {
+        match self {
+            InputSource::File(path) => path.clone(),
+            InputSource::Stdin => PathBuf::from("<stdin>"),
+        }
+    }
+}
+
+/// The directory inputs are read from: `$AOC_INPUT_DIR`, or `./data` when that is not set.
+pub fn input_dir() -> PathBuf {
+    input_dir_from(env::var_os(INPUT_DIR_VAR))
+}
+
+fn input_dir_from(input_dir: Option<OsString>) -> PathBuf {
+    input_dir
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR), PathBuf::from)
+}
+
+/// Works out which day an input file belongs to from its name, e.g. `day9.txt`,
+/// `sample9.txt` or `sample11_small.txt`.
+pub fn day_of_input(file_name: &str) -> Option<u8> {
+    let stem = file_name.strip_suffix(".txt")?;
+    let rest = stem
+        .strip_prefix("day")
+        .or_else(|| stem.strip_prefix("sample"))?;
+    let digits = rest
+        .find(|chr: char| !chr.is_ascii_digit())
+        .unwrap_or(rest.len());
+
+    match &rest[digits..] {
+        "" => rest[..digits].parse().ok(),
+        suffix if suffix.starts_with('_') => rest[..digits].parse().ok(),
+        _ => None,
+    }
+}
+
+/// Lists the inputs in `dir` that belong to a day, sorted by day and then file name.
+pub fn discover_inputs(dir: &Path) -> io::Result<Vec<(u8, String)>> {
+    let mut inputs = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let file_name = entry?.file_name().to_string_lossy().into_owned();
+        if let Some(day) = day_of_input(&file_name) {
+            inputs.push((day, file_name));
+        }
+    }
+
+    inputs.sort();
+    Ok(inputs)
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name().display())
+    }
+}
+
+#[cfg(test)]
+mod input_tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_explicit_path() {
+        assert_eq!(
+            InputSource::File(PathBuf::from("inputs/alice.txt")),
+            InputSource::resolve_with(7, Some("inputs/alice.txt"), Some("elsewhere".into()))
+        );
+    }
+
+    #[test]
+    fn test_resolve_stdin() {
+        assert_eq!(
+            InputSource::Stdin,
+            InputSource::resolve_with(7, Some("-"), None)
+        );
+    }
+
+    #[test]
+    fn test_resolve_input_dir() {
+        assert_eq!(
+            InputSource::File(PathBuf::from("/tmp/inputs/day7.txt")),
+            InputSource::resolve_with(7, None, Some("/tmp/inputs".into()))
+        );
+    }
+
+    #[test]
+    fn test_day_of_input() {
+        assert_eq!(Some(9), day_of_input("day9.txt"));
+        assert_eq!(Some(10), day_of_input("sample10.txt"));
+        assert_eq!(Some(11), day_of_input("sample11_small.txt"));
+        assert_eq!(None, day_of_input("answers.toml"));
+        assert_eq!(None, day_of_input("day9.txt.bak"));
+        assert_eq!(None, day_of_input("daytime.txt"));
+        assert_eq!(None, day_of_input("day9x.txt"));
+    }
+
+    #[test]
+    fn test_resolve_default() {
+        assert_eq!(
+            InputSource::File(PathBuf::from("./data/day7.txt")),
+            InputSource::resolve_with(7, None, None)
+        );
+        assert_eq!(
+            InputSource::File(PathBuf::from("./data/day7.txt")),
+            InputSource::resolve_with(7, None, Some("".into()))
+        );
+    }
+}
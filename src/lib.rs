@@ -0,0 +1,155 @@
+use std::{
+    env,
+    fmt::{self, Display},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+pub mod answers;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod parse;
+
+pub use error::ParseError;
+pub use grid::Grid;
+pub use input::InputSource;
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// The days that have a solver, in order.
+pub const DAYS: [u8; 11] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+
+/// A day's puzzle: how to parse its input and how to answer both parts from it.
+pub trait Day {
+    const DAY: u8;
+
+    type Input;
+    type Output: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Self::Output;
+    fn part2(input: &Self::Input) -> Self::Output;
+}
+
+/// The answer to a part that fails on some inputs: either the answer, or the
+/// reason there isn't one, shown as `error: <reason>`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Fallible<T, E>(pub std::result::Result<T, E>);
+
+impl<T: Display, E: Display> Display for Fallible<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Ok(answer) => write!(f, "{}", answer),
+            Err(err) => write!(f, "error: {}", err),
+        }
+    }
+}
+
+/// The answers from solving a day's input once, with the wall-clock time of each phase.
+pub struct Report {
+    pub parse_time: Duration,
+    /// The answer and time taken for each part, or `None` for parts that were not solved.
+    pub parts: [Option<(String, Duration)>; 2],
+}
+
+/// A puzzle solver that turns a day's raw input into the answers for each part.
+pub trait Solution {
+    /// Parses the input once and solves the given parts, timing each phase.
+    fn report(&self, input: &str, parts: &[u8]) -> Result<Report>;
+}
+
+impl<D: Day> Solution for D {
+    fn report(&self, input: &str, parts: &[u8]) -> Result<Report> {
+        let start = Instant::now();
+        let input = D::parse(input)?;
+        let parse_time = start.elapsed();
+
+        let time_part = |part: fn(&D::Input) -> D::Output| {
+            let start = Instant::now();
+            let answer = part(&input).to_string();
+            (answer, start.elapsed())
+        };
+
+        Ok(Report {
+            parse_time,
+            parts: [
+                parts.contains(&1).then(|| time_part(D::part1)),
+                parts.contains(&2).then(|| time_part(D::part2)),
+            ],
+        })
+    }
+}
+
+/// Returns the solver for the given day, if there is one.
+pub fn solution(day: u8) -> Option<Box<dyn Solution>> {
+    match day {
+        1 => Some(Box::new(day1::Day1)),
+        2 => Some(Box::new(day2::Day2)),
+        3 => Some(Box::new(day3::Day3)),
+        4 => Some(Box::new(day4::Day4)),
+        5 => Some(Box::new(day5::Day5)),
+        6 => Some(Box::new(day6::Day6)),
+        7 => Some(Box::new(day7::Day7)),
+        8 => Some(Box::new(day8::Day8)),
+        9 => Some(Box::new(day9::Day9)),
+        10 => Some(Box::new(day10::Day10)),
+        11 => Some(Box::new(day11::Day11)),
+        _ => None,
+    }
+}
+
+/// Solves both parts of a day and prints the answers. The input is read from the path given
+/// as the first argument (`-` for stdin), falling back to `$AOC_INPUT_DIR/dayN.txt` and then
+/// `./data/dayN.txt`. Input that fails to parse is reported as a diagnostic pointing at the
+/// bad token.
+pub fn run<D: Day>() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() > 1 {
+        eprintln!("Usage: day{} [input path, or - for stdin]", D::DAY);
+        return ExitCode::from(2);
+    }
+
+    let (_, parsed) = match read_input::<D>(args.first().map(|arg| arg.as_str())) {
+        Some(read) => read,
+        None => return ExitCode::FAILURE,
+    };
+
+    println!("Day {} part 1: {}", D::DAY, D::part1(&parsed));
+    println!("Day {} part 2: {}", D::DAY, D::part2(&parsed));
+
+    ExitCode::SUCCESS
+}
+
+/// Reads and parses a day's input from `path`, found as `run` finds it, and
+/// returns the text with what it parsed to. Anything that goes wrong is
+/// reported on stderr.
+pub fn read_input<D: Day>(path: Option<&str>) -> Option<(String, D::Input)> {
+    let source = InputSource::resolve(D::DAY, path);
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: could not read {}: {}", source, err);
+            return None;
+        }
+    };
+
+    match D::parse(&input) {
+        Ok(parsed) => Some((input, parsed)),
+        Err(err) => {
+            eprint!("{}", err.with_file(source.name()).render(&input));
+            None
+        }
+    }
+}
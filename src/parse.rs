@@ -0,0 +1,78 @@
+//! Parsers shared between the days' puzzle inputs.
+
+use crate::{Grid, ParseError, Result};
+
+/// Splits the input into one owned `String` per line.
+pub fn lines(input: &str) -> Vec<String> {
+    input.lines().map(|line| line.to_owned()).collect()
+}
+
+/// Parses a line of single digits such as "2199943210".
+pub fn digit_line<T: TryFrom<u32>>(line: &str) -> Result<Vec<T>> {
+    line.char_indices()
+        .map(|(i, chr)| {
+            chr.to_digit(10)
+                .and_then(|digit| T::try_from(digit).ok())
+                .ok_or_else(|| {
+                    ParseError::invalid_token(line, &line[i..i + chr.len_utf8()], "a digit")
+                })
+        })
+        .collect()
+}
+
+/// Parses a grid of single digits, one row per line. Every row must be the same width.
+pub fn digit_grid<T: TryFrom<u32>>(input: &str) -> Result<Grid<T>> {
+    let mut cells = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+
+    for (i, line) in input.lines().enumerate() {
+        let row = digit_line(line).map_err(|err| err.offset_lines(i))?;
+        let width = *width.get_or_insert(row.len());
+
+        if row.len() < width {
+            let expected = format!("{} digits", width);
+            return Err(ParseError::unexpected_end(line, &expected).offset_lines(i));
+        } else if row.len() > width {
+            let expected = format!("{} digits", width);
+            return Err(ParseError::invalid_token(line, &line[width..], &expected).offset_lines(i));
+        }
+
+        cells.extend(row);
+        height += 1;
+    }
+
+    Ok(Grid::from_vec(width.unwrap_or(0), height, cells))
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_line() {
+        assert_eq!(vec![2_u8, 1, 9, 9, 0], digit_line::<u8>("21990").unwrap());
+        assert_eq!(vec![5_i8, 4, 8], digit_line::<i8>("548").unwrap());
+    }
+
+    #[test]
+    fn test_digit_line_invalid() {
+        let err = digit_line::<u8>("21x90").unwrap_err();
+        assert_eq!("1:3: expected a digit, found `x`", err.to_string());
+    }
+
+    #[test]
+    fn test_digit_grid() {
+        let grid = digit_grid::<u8>("123\n456").unwrap();
+        assert_eq!(Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]), grid);
+    }
+
+    #[test]
+    fn test_digit_grid_ragged() {
+        let err = digit_grid::<u8>("123\n45\n678").unwrap_err();
+        assert_eq!("2:3: expected 3 digits, found end of line", err.to_string());
+
+        let err = digit_grid::<u8>("123\n4567").unwrap_err();
+        assert_eq!("2:4: expected 3 digits, found `7`", err.to_string());
+    }
+}